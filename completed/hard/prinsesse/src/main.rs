@@ -1,23 +1,22 @@
-use std::io::Write;
-fn read_vec<T: std::str::FromStr, B: std::io::BufRead>(bufreader: &mut B) -> Vec<T>
-where
-    T::Err: std::fmt::Debug,
-{
-    let mut line = String::new();
-    bufreader.read_line(&mut line).unwrap();
-    line.split_whitespace()
-        .map(|tok| tok.parse::<T>().expect("Failed to parse input"))
-        .collect()
+// noinspection
+#[allow(unused)]
+macro_rules! import {
+    ($name:ident) => {
+        #[cfg(not(feature = "libfreuden"))]
+        mod $name;
+        #[cfg(not(feature = "libfreuden"))]
+        #[allow(unused_imports)]
+        use $name::*;
+
+        #[cfg(feature = "libfreuden")]
+        #[allow(unused_imports)]
+        use libfreuden::$name::*;
+    };
 }
 
-fn read_one<T: std::str::FromStr, B: std::io::BufRead>(bufreader: &mut B) -> T
-where
-    T::Err: std::fmt::Debug,
-{
-    let mut line = String::new();
-    bufreader.read_line(&mut line).unwrap();
-    line.trim().parse::<T>().unwrap()
-}
+import!(input);
+
+use std::io::Write;
 
 #[derive(Copy, Clone)]
 struct ScenarioConfiguration {
@@ -29,7 +28,7 @@ struct ScenarioConfiguration {
 }
 
 struct Scenario<B: std::io::BufRead, W: std::io::Write> {
-    buf_reader: B,
+    input: InputSource<B>,
     buf_writer: W,
     configuration: ScenarioConfiguration,
 }
@@ -37,9 +36,15 @@ struct Scenario<B: std::io::BufRead, W: std::io::Write> {
 const SLEPT_ON_PEA: bool = true;
 
 impl<B: std::io::BufRead, W: std::io::Write> Scenario<B, W> {
-    fn new(mattresses: u16, nights: u16, penalty: u16, buf_reader: B, buf_writer: W) -> Self {
+    fn new(
+        mattresses: u16,
+        nights: u16,
+        penalty: u16,
+        input: InputSource<B>,
+        buf_writer: W,
+    ) -> Self {
         Scenario {
-            buf_reader,
+            input,
             buf_writer,
             configuration: ScenarioConfiguration {
                 mattresses,
@@ -59,7 +64,7 @@ impl<B: std::io::BufRead, W: std::io::Write> Scenario<B, W> {
 
     fn make_query(&mut self, mattress_indices: std::ops::Range<u16>) -> bool {
         self.print_mattress_query(mattress_indices);
-        read_one::<u8, _>(&mut self.buf_reader) != 0
+        self.input.one::<u8>() != 0
     }
 
     fn print_mattress_query(&mut self, mattress_indices: std::ops::Range<u16>) {
@@ -142,11 +147,14 @@ impl<B: std::io::BufRead, W: std::io::Write> Scenario<B, W> {
 }
 
 fn main() {
-    let mut stdin = std::io::BufReader::with_capacity(64, std::io::stdin().lock());
+    let mut input = InputSource::new(std::io::BufReader::with_capacity(
+        64,
+        std::io::stdin().lock(),
+    ));
 
-    let [mattresses, nights, penalty]: [u16; 3] = read_vec(&mut stdin).try_into().unwrap();
+    let [mattresses, nights, penalty]: [u16; 3] = input.array();
 
-    let mut scenario = Scenario::new(mattresses, nights, penalty, stdin, std::io::stdout().lock());
+    let mut scenario = Scenario::new(mattresses, nights, penalty, input, std::io::stdout().lock());
     while scenario.configuration.mattresses > 1
         && scenario.configuration.nights > 0
         && scenario.configuration.nights <= nights
@@ -8,7 +8,7 @@ fn run_scenario(mattresses: u16, nights: u16, penalty: u16, pea_location: u16) -
         mattresses,
         nights,
         penalty,
-        std::io::stdin().lock(),
+        InputSource::new(std::io::stdin().lock()),
         std::io::stdout(),
     );
 
@@ -183,3 +183,13 @@ fn test_group6_26_somewhere() {
         run_scenario(1000, 26, 5, mattress);
     }
 }
+
+#[test]
+fn test_scripted_answers() {
+    let mut scenario = Scenario::new(2, 2, 1, InputSource::new(&b"1\n"[..]), Vec::new());
+    while scenario.configuration.mattresses > 1 && scenario.configuration.nights > 0 {
+        scenario.scenario_step();
+    }
+    assert_eq!(scenario.configuration.offset, 0);
+    assert_eq!(scenario.buf_writer, b"? 0\n");
+}
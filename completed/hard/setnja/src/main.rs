@@ -17,25 +17,20 @@ import!(input);
 import!(mediocre_bigint);
 
 fn solve(walk: &str) -> String {
-    use std::str::FromStr;
-
-    let mut accumulator = MediocreBigint::from_str("1").unwrap();
+    let mut accumulator = MediocreBigint::from(1u64);
     let mut multiplexor: MediocreBigint = accumulator.clone();
 
-    let five = MediocreBigint::from_str("5").unwrap();
-    let three = MediocreBigint::from_str("3").unwrap();
-    let two = MediocreBigint::from_str("2").unwrap();
     for step in walk.chars() {
         match step {
             '*' => {
-                accumulator *= &five;
+                accumulator *= 5;
                 accumulator += &multiplexor;
-                multiplexor *= &three;
+                multiplexor *= 3;
             }
             'P' => {}
-            'L' => accumulator *= &two,
+            'L' => accumulator *= 2,
             'R' => {
-                accumulator *= &two;
+                accumulator *= 2;
                 accumulator += &multiplexor;
             }
             _ => panic!("This should not happen"),
@@ -482,223 +477,4 @@ mod setnja_tests {
     fn test_solve_left_star_right() {
         assert_eq!(solve("L*R"), "25");
     }
-
-    #[test]
-    fn test_add_zero() {
-        let a = MediocreBigint {
-            digits: vec![1, 0, 0, 1, 1],
-        };
-        let zero = MediocreBigint::new();
-        assert_eq!(zero.to_string(), "0");
-        let c = a.clone() + zero.clone();
-        assert_eq!(c, a);
-
-        let d = zero + a.clone();
-        assert_eq!(d, a);
-    }
-
-    #[test]
-    fn test_mul_zero() {
-        let a = MediocreBigint {
-            digits: vec![1, 0, 0, 1, 1],
-        };
-        let zero = MediocreBigint::new();
-        assert_eq!(zero.to_string(), "0");
-        let c = a.clone() * zero.clone();
-        assert_eq!(c.to_string(), "0");
-
-        let d = zero.clone() * a.clone();
-        assert_eq!(d.to_string(), "0");
-    }
-
-    #[test]
-    fn test_mul_small() {
-        let a = MediocreBigint { digits: vec![2] };
-        let b = MediocreBigint { digits: vec![3] };
-        let c = a * b;
-        assert_eq!(c.digits, vec![6]);
-    }
-
-    #[test]
-    fn test_mul_carry() {
-        let a = MediocreBigint { digits: vec![7] };
-        let b = MediocreBigint { digits: vec![8] };
-        let c = a * b;
-        assert_eq!(c.to_string(), "56");
-    }
-
-    #[test]
-    fn test_mul_larger() {
-        use std::str::FromStr;
-        let a = MediocreBigint::from_str("123").unwrap();
-        let b = MediocreBigint::from_str("456").unwrap();
-        let c = a * b;
-        assert_eq!(c, MediocreBigint::from_str("56088").unwrap());
-    }
-
-    #[test]
-    fn test_add_small() {
-        let a = MediocreBigint { digits: vec![2] };
-        let b = MediocreBigint { digits: vec![2] };
-        let c = a.clone() + b;
-        assert_eq!(c.digits, vec![4]);
-    }
-
-    #[test]
-    fn test_add_big() {
-        let a = MediocreBigint { digits: vec![2] };
-        let b = MediocreBigint {
-            digits: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2],
-        };
-        let c = a.clone() + b;
-        assert_eq!(c.digits, vec![2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
-    }
-
-    #[test]
-    fn test_add_carry() {
-        let a = MediocreBigint::from_str("1776").unwrap();
-        let b = MediocreBigint::from_str("76").unwrap();
-        let mut c = a + b;
-        c.normalize();
-        use std::str::FromStr;
-        assert_eq!(
-            c,
-            MediocreBigint::from_str(&(1776 + 76).to_string()).unwrap()
-        );
-    }
-
-    #[test]
-    fn test_add_mega_denorm() {
-        let a = MediocreBigint {
-            digits: vec![MediocreDigitSize::MAX / 2],
-        };
-        let mut b = a.clone();
-        b.normalize();
-        assert_eq!(a.digits, vec![MediocreDigitSize::MAX / 2]);
-        assert_eq!(
-            b.digits,
-            vec![
-                (MediocreDigitSize::MAX / 2) % BIGINT_LIMIT as MediocreDigitSize,
-                (MediocreDigitSize::MAX / 2) / BIGINT_LIMIT as MediocreDigitSize
-            ]
-        );
-
-        let mut c = a.clone() + a.clone();
-        let mut d = a.clone() + b.clone();
-        let mut e = b.clone() + b.clone();
-
-        c.normalize();
-        d.normalize();
-        e.normalize();
-        assert_eq!(c, d);
-        assert_eq!(d, e);
-    }
-
-    #[test]
-    fn test_simple_mega() {
-        use std::str::FromStr;
-        let a = MediocreBigint::from_str("211111111111").unwrap();
-        let b = MediocreBigint::from_str("4").unwrap();
-        assert_eq!((a * b).to_string(), "844444444444");
-    }
-
-    #[test]
-    fn test_display_bookends() {
-        use std::str::FromStr;
-        let a = MediocreBigint::from_str("7700000000000000000000042").unwrap();
-        assert_eq!(a.to_string(), "7700000000000000000000042");
-    }
-
-    #[test]
-    fn test_create() {
-        let int = MediocreBigint::new();
-        assert_eq!(int.digits.len(), 0);
-    }
-
-    #[test]
-    fn test_empty_is_zero() {
-        let int = MediocreBigint::new();
-        assert_eq!(int.to_string(), "0");
-    }
-
-    #[test]
-    fn test_is_normalized() {
-        let a = MediocreBigint { digits: vec![] };
-        let b = MediocreBigint {
-            digits: vec![2, 1, 1],
-        };
-        let c = MediocreBigint {
-            digits: vec![0, 0, 1, (BIGINT_LIMIT + 1) as MediocreDigitSize],
-        };
-        assert!(a.is_normalized());
-        assert!(b.is_normalized());
-        assert_eq!(c.is_normalized(), false);
-    }
-
-    #[test]
-    fn test_clone() {
-        let a = MediocreBigint {
-            digits: vec![1, 1, 1, 2, 1],
-        };
-        let mut b = a.clone();
-        assert_eq!(a, b);
-        b.digits[0] = 2;
-        assert_ne!(a, b);
-    }
-
-    #[test]
-    fn test_normalize() {
-        let mut a = MediocreBigint {
-            digits: vec![1, BIGINT_LIMIT as MediocreDigitSize, 0, 1, 1],
-        };
-        let b = a.clone();
-        assert_eq!(a, b);
-        a.normalize();
-        assert_ne!(a, b);
-        assert!(a.is_normalized());
-        assert!(a.digits.eq(&vec![1, 0, 1, 1, 1]));
-    }
-
-    #[test]
-    fn test_normalize_into_carry() {
-        let mut a = MediocreBigint {
-            digits: vec![
-                1,
-                BIGINT_LIMIT as MediocreDigitSize,
-                BIGINT_LIMIT as MediocreDigitSize,
-                0,
-                1,
-            ],
-        };
-        let b = a.clone();
-        assert_eq!(a, b);
-        a.normalize();
-        assert_ne!(a, b);
-        assert!(a.is_normalized());
-        assert!(a.digits.eq(&vec![1, 0, 1, 1, 1]));
-    }
-
-    #[test]
-    fn test_normalize_zero() {
-        let mut a = MediocreBigint { digits: vec![] };
-        a.normalize();
-        assert_eq!(a.digits, vec![]);
-    }
-
-    #[test]
-    fn test_ten_is_ten() {
-        use std::str::FromStr;
-        let mut a = MediocreBigint::from_str("10").unwrap();
-        a.normalize();
-        assert_eq!(a.to_string(), "10");
-    }
-
-    #[test]
-    fn test_normalize_create_digit() {
-        let mut a = MediocreBigint {
-            digits: vec![BIGINT_LIMIT as MediocreDigitSize],
-        };
-        a.normalize();
-        assert_eq!(a.digits, vec![0, 1]);
-    }
 }
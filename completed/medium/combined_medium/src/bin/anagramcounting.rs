@@ -18,7 +18,6 @@ import!(mediocre_bigint);
 
 fn main() {
     use std::io::Read;
-    use std::str::FromStr;
     let mut all_lines = String::new();
     std::io::stdin()
         .lock()
@@ -27,16 +26,17 @@ fn main() {
     for untrimmed in all_lines.split_terminator('\n') {
         let line = untrimmed.trim();
 
-        let mut accumulator: MediocreBigint = MediocreBigint::from_str("1").unwrap();
+        // Anagrams are the multinomial n! / (c_1! c_2! ...) over the letter counts, built one
+        // letter at a time so every intermediate value is the multinomial of a prefix
+        let mut accumulator: MediocreBigint = MediocreBigint::from(1u64);
         let mut charmap = std::collections::HashMap::<char, usize>::new();
         for (i, char) in line.chars().enumerate() {
             let letter_count = i + 1;
-            *charmap.entry(char).or_insert(0) += 1;
+            let char_count = charmap.entry(char).or_insert(0);
+            *char_count += 1;
 
-            let char_count = *charmap.entry(char).or_insert(0);
-            accumulator.normalize();
-            accumulator *= MediocreBigint::from_str(&letter_count.to_string()).unwrap();
-            accumulator /= MediocreBigint::from_str(&char_count.to_string()).unwrap();
+            accumulator *= letter_count as u64;
+            accumulator /= *char_count as u64;
         }
         println!("{}", accumulator);
     }
@@ -20,7 +20,9 @@ macro_rules! import {
     };
 }
 
+import!(big_rational);
 import!(input);
+import!(mediocre_bigint);
 
 macro_rules! kattis_struct {
     ($name:ident { $($field_name:ident : $field_type:ty),* }) => {
@@ -43,7 +45,15 @@ macro_rules! kattis_struct {
     }
 
 fn main() {
-    europeantrip();
+    // Archived solutions run with `cargo run -- <problem id>` or `KATTIS_PROBLEM=<problem id>`,
+    // without either the active one below runs, which is what gets submitted
+    match std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("KATTIS_PROBLEM").ok())
+    {
+        Some(problem_id) => run_problem(&problem_id),
+        None => europeantrip(),
+    }
 }
 
 fn europeantrip() {
@@ -394,55 +404,14 @@ fn longest_common_subsequence(a: &[u8], b: &[u8]) -> usize {
 }
 
 fn pointcoloring() {
-    use std::io::{self, Read, Write};
+    use std::io::{self, Write};
 
-    // prototype FastReader: read entire stdin into a byte buffer and parse ASCII digits quickly
-    struct FastReader {
-        buf: Vec<u8>,
-        idx: usize,
-        len: usize,
-    }
-    impl FastReader {
-        fn new() -> Self {
-            let mut buf = Vec::with_capacity(1 << 20);
-            io::stdin().read_to_end(&mut buf).unwrap();
-            let len = buf.len();
-            FastReader { buf, idx: 0, len }
-        }
-        #[inline]
-        fn skip_ws(&mut self) {
-            while self.idx < self.len {
-                let b = unsafe { *self.buf.get_unchecked(self.idx) };
-                if b > b' ' {
-                    break;
-                }
-                self.idx += 1;
-            }
-        }
-        #[inline]
-        fn next_usize(&mut self) -> usize {
-            self.skip_ws();
-            let mut v: usize = 0;
-            while self.idx < self.len {
-                let b = unsafe { *self.buf.get_unchecked(self.idx) };
-                if b.is_ascii_digit() {
-                    v = v * 10 + (b - b'0') as usize;
-                    self.idx += 1;
-                } else {
-                    break;
-                }
-            }
-            v
-        }
-    }
-
-    let mut rdr = FastReader::new();
-    let t = rdr.next_usize();
+    let scanner = Scanner::stdin();
+    let t: usize = scanner.next();
 
     let mut out = String::with_capacity(t * 3);
     for _ in 0..t {
-        let x: usize = rdr.next_usize();
-        let y: usize = rdr.next_usize();
+        let [x, y]: [usize; 2] = scanner.next();
 
         // For each (x, y), color points (x+2^i, y) and (x, y+2^i) with color i+1, all other points -1
         let xor = x ^ y;
@@ -817,40 +786,6 @@ fn hastyhash() {
     }
 }
 
-struct FactHelper {
-    answers_cache: Vec<Option<u64>>,
-}
-impl FactHelper {
-    fn new(max: usize) -> FactHelper {
-        FactHelper {
-            answers_cache: vec![None; max],
-        }
-    }
-
-    fn fact(&mut self, input: u64) -> u64 {
-        if input == 1 || input == 0 {
-            return 1;
-        } else if let Some(answer) = self.answers_cache[(input - 1) as usize] {
-            return answer;
-        }
-        input * self.fact(input - 1)
-    }
-
-    fn nchoose(&mut self, n: u64, choose: u64) -> u64 {
-        self.fact(n)
-            .div_ceil(self.fact(choose) * self.fact(n.saturating_sub(choose)))
-    }
-}
-
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while b != 0 {
-        let temp = b;
-        b = a % b;
-        a = temp;
-    }
-    a
-}
-
 fn remoatseating() {
     const SUB_SMALL: u64 = 2;
     const SUB_BIG: u64 = 3;
@@ -863,14 +798,47 @@ fn remoatseating() {
     let sub_count = teams.iter().filter(|&&x| x == SUB_BIG).count() as u32;
     let dom_count = teams.iter().filter(|&&x| x == FULL_TEAM).count() as u32;
 
-    let mut fact = FactHelper::new(20);
-    let internal_permutations = fact.fact(SUB_SMALL).pow(mini_count)
-        * fact.fact(SUB_BIG).pow(sub_count)
-        * fact.fact(FULL_TEAM).pow(dom_count);
-    let team_configurations = fact.fact((mini_count + sub_count + dom_count) as u64);
-    let numerator = internal_permutations * team_configurations;
-    let denominator = fact.fact(teams.iter().sum());
+    let internal_permutations = MediocreBigint::factorial(SUB_SMALL).pow(mini_count)
+        * MediocreBigint::factorial(SUB_BIG).pow(sub_count)
+        * MediocreBigint::factorial(FULL_TEAM).pow(dom_count);
+    let team_configurations =
+        MediocreBigint::factorial((mini_count + sub_count + dom_count) as u64);
+    let seatings = MediocreBigint::factorial(teams.iter().sum());
 
-    let divisor = gcd(numerator, denominator);
-    println!("{}/{}", numerator / divisor, denominator / divisor);
+    let probability = BigRational::from(internal_permutations * team_configurations)
+        / BigRational::from(seatings);
+    println!("{}", probability);
+}
+
+/// Every solution in this file, keyed by its Kattis problem id
+const PROBLEMS: &[(&str, fn())] = &[
+    ("europeantrip", europeantrip),
+    ("digits", digits),
+    ("expeditiouscubing", expeditiouscubing),
+    ("simplearithmetic", simplearithmetic),
+    ("powerstrings", powerstrings),
+    ("longestcommonsubsequence", longestcommonsubsequence),
+    ("dnasimilarity", dnasimilarity),
+    ("pointcoloring", pointcoloring),
+    ("glitchingscreen", glitchingscreen),
+    ("repeatedsubsequence", repeatedsubsequence),
+    ("rectsect", rectsect),
+    ("brentering", brentering),
+    ("wherehasmylittledoggone", wherehasmylittledoggone),
+    ("everysecond", everysecond),
+    ("alice", alice),
+    ("lotsofliquid", lotsofliquid),
+    ("atmmaintenance", atm_maintenance),
+    ("hastyhash", hastyhash),
+    ("remoatseating", remoatseating),
+];
+
+fn run_problem(problem_id: &str) {
+    match PROBLEMS.iter().find(|(id, _)| *id == problem_id) {
+        Some((_, solve)) => solve(),
+        None => {
+            eprintln!("no solution for problem {} in this file", problem_id);
+            std::process::exit(1);
+        }
+    }
 }
@@ -16,6 +16,7 @@ macro_rules! import {
 
 import!(input);
 import!(fenwick_tree);
+import!(output);
 
 struct Op {
     index: IndexType,
@@ -53,112 +54,29 @@ fn do_fenwick_operations(fenwick: &mut FenwickTree, operations_list: Vec<Op>) ->
     answers
 }
 
-fn parse_bytes(bytes: &[u8]) -> (i64, usize) {
-    let mut index = 0usize;
-    while index < bytes.len() {
-        let b = unsafe { *bytes.get_unchecked(index) };
-        if b > b' ' {
-            break;
-        }
-        index += 1;
-    }
-    let is_negative = if unsafe { *bytes.get_unchecked(index) } == b'-' {
-        index += 1;
-        true
-    } else {
-        false
-    };
-    let mut v: usize = 0;
-    while index < bytes.len() {
-        let b = unsafe { *bytes.get_unchecked(index) };
-        if b.is_ascii_digit() {
-            v = v * 10 + (b - b'0') as usize;
-            index += 1;
-        } else {
-            break;
-        }
-    }
-    if is_negative {
-        (-(v as i64), index)
-    } else {
-        (v as i64, index)
-    }
-}
-
-fn run_problem<R: std::io::Read, W: std::io::Write>(read_source: R, mut write_source: W) {
-    let mut bufreader = std::io::BufReader::new(read_source);
-    let [array_len, operations_count]: [usize; 2] =
-        read_vec_source(&mut bufreader).try_into().unwrap();
+fn run_problem<R: std::io::Read, W: std::io::Write>(read_source: R, write_source: W) {
+    let scanner = Scanner::new(read_source);
+    let [array_len, operations_count]: [usize; 2] = scanner.next();
     let mut operations_list = Vec::with_capacity(operations_count);
 
-    let mut all_lines = String::new();
-    std::io::Read::read_to_string(&mut bufreader, &mut all_lines).unwrap();
-
-    let mut index = 0usize;
-    let bytes = all_lines.as_bytes();
-    'outer: while index < bytes.len() {
-        loop {
-            if index >= bytes.len() {
-                break 'outer;
+    while !scanner.is_empty() {
+        match scanner.next::<&[u8]>() {
+            b"?" => operations_list.push(Op::new_query(scanner.next())),
+            b"+" => {
+                let op_index: IndexType = scanner.next();
+                operations_list.push(Op::new_increment(op_index, scanner.next()));
             }
-            let b = unsafe { *bytes.get_unchecked(index) };
-            if b > b' ' {
-                break;
-            }
-            index += 1;
-        }
-        let opchar = unsafe { *bytes.get_unchecked(index) };
-        index += 2;
-        let (op_index, offset) = parse_bytes(&bytes[index..]);
-        index += offset;
-        if opchar == b'?' {
-            operations_list.push(Op::new_query(op_index as IndexType));
-        } else if opchar == b'+' {
-            let (op_value, offset) = parse_bytes(&bytes[index..]);
-            index += offset;
-            operations_list.push(Op::new_increment(
-                op_index as IndexType,
-                op_value as ValueType,
-            ));
-        } else {
-            break;
+            _ => break,
         }
     }
 
     let mut fenwick = FenwickTree::new(array_len);
     let answers = do_fenwick_operations(&mut fenwick, operations_list);
 
-    let charholder = convert_answers_to_printbuffer(answers);
-    write_source.write_all(charholder.as_slice()).unwrap();
-    write_source.flush().unwrap();
-}
-
-fn convert_answers_to_printbuffer(answers: Vec<ValueType>) -> Vec<u8> {
-    let mut charholder = Vec::<u8>::with_capacity(1024 * 1024 * 64);
+    let mut writer = FastWriter::new(write_source);
     for answer in answers {
-        if answer.is_negative() {
-            charholder.push(b'-');
-        } else if answer == 0 {
-            charholder.push(b'0');
-            charholder.push(b'\n');
-            continue;
-        }
-
-        let mut temp = answer.unsigned_abs() as IndexType;
-
-        let digit_count = (temp.checked_ilog10().unwrap() + 1) as usize;
-        let mut offset = 1usize;
-        charholder.append(&mut vec![b' '; digit_count]);
-        let end = charholder.len();
-        while temp > 0 {
-            let modulo = (temp % 10) as u8;
-            temp /= 10;
-            charholder[end - offset] = b'0' + modulo;
-            offset += 1;
-        }
-        charholder.push(b'\n');
+        writer.write_line(answer);
     }
-    charholder
 }
 
 fn main() {
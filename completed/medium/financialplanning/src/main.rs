@@ -1,29 +1,25 @@
-fn read_vec<T: std::str::FromStr>() -> Vec<T>
-where
-    T::Err: std::fmt::Debug,
-{
-    let mut line = String::new();
-    std::io::stdin().read_line(&mut line).unwrap();
-    line.split_whitespace()
-        .map(|tok| tok.parse::<T>().expect("Failed to parse input"))
-        .collect()
-}
+// noinspection
+#[allow(unused)]
+macro_rules! import {
+    ($name:ident) => {
+        #[cfg(not(feature = "libfreuden"))]
+        mod $name;
+        #[cfg(not(feature = "libfreuden"))]
+        #[allow(unused_imports)]
+        use $name::*;
 
-fn try_read_array<T: std::str::FromStr, const K: usize, E: std::fmt::Debug>() -> Result<[T; K], E>
-where
-    T::Err: std::fmt::Debug,
-    [T; K]: TryFrom<Vec<T>, Error = E>,
-{
-    read_vec::<T>().try_into()
+        #[cfg(feature = "libfreuden")]
+        #[allow(unused_imports)]
+        use libfreuden::$name::*;
+    };
 }
 
-fn read_array<T: std::str::FromStr, const K: usize, E: std::fmt::Debug>() -> [T; K]
-where
-    T::Err: std::fmt::Debug,
-    [T; K]: TryFrom<Vec<T>, Error = E>,
-{
-    try_read_array().unwrap()
-}
+import!(input);
+
+kattis_input!(Input {
+    header: [u64; 2],
+    investments: Vec<[u64; 2]> [header[0]]
+});
 
 #[derive(Debug, Default, Copy, Clone)]
 struct Investment {
@@ -48,12 +44,13 @@ impl From<[u64; 2]> for Investment {
 }
 
 fn main() {
-    let [number_options, retirement_plan]: [u64; 2] = read_array();
-    let mut investments = Vec::<Investment>::with_capacity(number_options as usize);
-    for _ in 0..number_options {
-        let investment: Investment = read_array().into();
-        investments.push(investment);
-    }
+    let input: Input = read_record();
+    let [_, retirement_plan] = input.header;
+    let mut investments: Vec<Investment> = input
+        .investments
+        .into_iter()
+        .map(Investment::from)
+        .collect();
 
     investments.sort_by(|a, b| a.payoff_time().cmp(&b.payoff_time()));
 
@@ -22,7 +22,15 @@ macro_rules! import {
 import!(input);
 
 fn main() {
-    leftbeehind();
+    // Archived solutions run with `cargo run -- <problem id>` or `KATTIS_PROBLEM=<problem id>`,
+    // without either the active one below runs, which is what gets submitted
+    match std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("KATTIS_PROBLEM").ok())
+    {
+        Some(problem_id) => run_problem(&problem_id),
+        None => leftbeehind(),
+    }
 }
 
 fn leftbeehind() {
@@ -2338,3 +2346,162 @@ fn skak() {
 fn bergmal() {
     println!("{}", read_str());
 }
+
+/// Every solution in this file, keyed by its Kattis problem id
+const PROBLEMS: &[(&str, fn())] = &[
+    ("leftbeehind", leftbeehind),
+    ("quiteaproblem", quiteaproblem),
+    ("simon", simon),
+    ("quickbrownfox", quickbrownfox),
+    ("rectanglearea", rectanglearea),
+    ("counting", counting),
+    ("popcount", popcount),
+    ("findingana", findingana),
+    ("brokencalculator", brokencalculator),
+    ("lostlineup", lostlineup),
+    ("eligibility", eligibility),
+    ("inputscandal", inputscandal),
+    ("tolower", tolower),
+    ("buka", buka),
+    ("averagecharacter", averagecharacter),
+    ("undeadoralive", undeadoralive),
+    ("testdrive", testdrive),
+    ("temperature", temperature),
+    ("stafur", stafur),
+    ("helpaphd", helpaphd),
+    ("sith", sith),
+    ("provincesandgold", provincesandgold),
+    ("onechicken", onechicken),
+    ("judgingmoose", judgingmoose),
+    ("internationaldates", internationaldates),
+    ("vajningsplikt", vajningsplikt),
+    ("moscowdream", moscowdream),
+    ("mork", mork),
+    ("mergjadmal", mergjadmal),
+    ("laptopsticker", laptopsticker),
+    ("hvertskalmaeta", hvertskalmaeta),
+    ("ginfizz", ginfizz),
+    ("conteststruggles", conteststruggles),
+    ("chardonnay", chardonnay),
+    ("canadianseh", canadianseh),
+    ("blueberrywaffle", blueberrywaffle),
+    ("titlecost", titlecost),
+    ("shandy", shandy),
+    ("gcvwr", gcvwr),
+    ("ekkidaudi", ekkidaudi),
+    ("betting", betting),
+    ("thelastproblem", thelastproblem),
+    ("romans", romans),
+    ("planina", planina),
+    ("heroesofvelmar", heroesofvelmar),
+    ("greetings2", greetings2),
+    ("fimmtudagstilbod", fimmtudagstilbod),
+    ("dartscores", dartscores),
+    ("zyxab", zyxab),
+    ("rollingthedice", rollingthedice),
+    ("shatteredcake", shatteredcake),
+    ("numberreduction", numberreduction),
+    ("sifferprodukt", sifferprodukt),
+    ("veci", veci),
+    ("karte", karte),
+    ("modulo", modulo),
+    ("bitteeinbit", bitteeinbit),
+    ("threeblindmice", threeblindmice),
+    ("matchstickmen", matchstickmen),
+    ("fastestestfunction", fastestestfunction),
+    ("abovesealevel", abovesealevel),
+    ("equalshots", equalshots),
+    ("grading", grading),
+    ("knotknowledge", knotknowledge),
+    ("sumkindofproblem", sumkindofproblem),
+    ("skener", skener),
+    ("parkingpandemonium", parkingpandemonium),
+    ("cosmicpathoptimization", cosmicpathoptimization),
+    ("fizzbuzz", fizzbuzz),
+    ("mclimb", mclimb),
+    ("13floors", _13floors),
+    ("flatterland", flatterland),
+    ("hackaholics", hackaholics),
+    ("catinabox", catinabox),
+    ("isithalloween", isithalloween),
+    ("edays", edays),
+    ("hissingmicrophone", hissingmicrophone),
+    ("batterup", batterup),
+    ("filip", filip),
+    ("faktor", faktor),
+    ("grassseed", grassseed),
+    ("pot", pot),
+    ("pet", pet),
+    ("reversebinary", reversebinary),
+    ("cetvrta", cetvrta),
+    ("sibice", sibice),
+    ("wakeupcall", wakeupcall),
+    ("grafaholur", grafaholur),
+    ("triangelfabriken", triangelfabriken),
+    ("monopol", monopol),
+    ("hakkari", hakkari),
+    ("lastfactorialdigit", lastfactorialdigit),
+    ("autori", autori),
+    ("r2", r2),
+    ("vedurheidar", vedurheidar),
+    ("barcelona", barcelona),
+    ("heysata", heysata),
+    ("pobudget", pobudget),
+    ("umferd", umferd),
+    ("fjoldibokstafa", fjoldibokstafa),
+    ("kikiboba", kikiboba),
+    ("leynithjonusta", leynithjonusta),
+    ("ofugsnuid", ofugsnuid),
+    ("aterriblefortress", aterriblefortress),
+    ("isyavowel", isyavowel),
+    ("addingtrouble", addingtrouble),
+    ("whichisgreater", whichisgreater),
+    ("countthevowels", countthevowels),
+    ("twosum", twosum),
+    ("triarea", triarea),
+    ("nsum", nsum),
+    ("digitswap", digitswap),
+    ("sorttwonumbers", sorttwonumbers),
+    ("echoechoecho", echoechoecho),
+    ("addtwonumbers", addtwonumbers),
+    ("jackolanternjuxtaposition", jackolanternjuxtaposition),
+    ("qaly", qaly),
+    ("quadrant", quadrant),
+    ("twostones", twostones),
+    ("carrots", carrots),
+    ("dfyrirdreki", dfyrirdreki),
+    ("bestagjofin", bestagjofin),
+    ("parking2", parking2),
+    ("magictrick", magictrick),
+    ("bookingaroom", bookingaroom),
+    ("lineup", lineup),
+    ("fyi", fyi),
+    ("tarifa", tarifa),
+    ("coffeecupcombo", coffeecupcombo),
+    ("jumbojavelin", jumbojavelin),
+    ("cold", cold),
+    ("oddities", oddities),
+    ("timeloop", timeloop),
+    ("bijele", bijele),
+    ("goggi", goggi),
+    ("blandadbest", blandadbest),
+    ("decimaldeletion", decimaldeletion),
+    ("spritt", spritt),
+    ("ameriskur", ameriskur),
+    ("oddecho", oddecho),
+    ("hipphipphurra", hipphipphurra),
+    ("bladra2", bladra2),
+    ("dagatal", dagatal),
+    ("skak", skak),
+    ("bergmal", bergmal),
+];
+
+fn run_problem(problem_id: &str) {
+    match PROBLEMS.iter().find(|(id, _)| *id == problem_id) {
+        Some((_, solve)) => solve(),
+        None => {
+            eprintln!("no solution for problem {} in this file", problem_id);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use crate::mediocre_bigint::{MediocreBigint, SignedMediocreBigint};
+
+/// BigRational is an exact fraction of big integers for answers like "print the probability as
+/// p/q" once the factorials outgrow `u64`.
+/// It is always in lowest terms with the sign on the numerator and a positive denominator, so
+/// the derived equality and hashing compare values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigRational {
+    numerator: SignedMediocreBigint,
+    denominator: MediocreBigint,
+}
+
+impl BigRational {
+    pub fn new() -> BigRational {
+        BigRational::default()
+    }
+
+    /// `numerator / denominator` in lowest terms, panics when the denominator is zero
+    pub fn from_parts(numerator: SignedMediocreBigint, denominator: MediocreBigint) -> BigRational {
+        if denominator.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        let mut value = BigRational {
+            numerator,
+            denominator,
+        };
+        value.reduce();
+        value
+    }
+
+    pub fn numerator(&self) -> &SignedMediocreBigint {
+        &self.numerator
+    }
+
+    /// Always positive
+    pub fn denominator(&self) -> &MediocreBigint {
+        &self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.magnitude().is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numerator.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == MediocreBigint::from(1u64)
+    }
+
+    pub fn abs(&self) -> BigRational {
+        BigRational {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    /// `1 / self`, panics for zero
+    pub fn recip(&self) -> BigRational {
+        BigRational::from_parts(
+            SignedMediocreBigint::from_magnitude(self.is_negative(), self.denominator.clone()),
+            self.numerator.magnitude().clone(),
+        )
+    }
+
+    /// The value written with exactly `places` digits after the decimal point, rounding half
+    /// away from zero. Unlike printing an `f64`, every digit is exact.
+    pub fn to_decimal_string(&self, places: usize) -> String {
+        let scaled =
+            self.numerator.magnitude().clone() * MediocreBigint::from(10u64).pow(places as u32);
+        let (mut rounded, remainder) = scaled.div_rem(&self.denominator);
+        if remainder * 2 >= self.denominator {
+            rounded += 1;
+        }
+
+        let digits = format!("{:0>width$}", rounded, width = places + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - places);
+        let sign = if self.is_negative() && !rounded.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        if places == 0 {
+            format!("{}{}", sign, whole)
+        } else {
+            format!("{}{}.{}", sign, whole, fraction)
+        }
+    }
+
+    fn reduce(&mut self) {
+        let divisor = self.numerator.magnitude().gcd(&self.denominator);
+        if divisor == MediocreBigint::from(1u64) {
+            return;
+        }
+        let numerator = self.numerator.magnitude().div_rem(&divisor).0;
+        self.numerator = SignedMediocreBigint::from_magnitude(self.is_negative(), numerator);
+        self.denominator = self.denominator.div_rem(&divisor).0;
+    }
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        BigRational::from(SignedMediocreBigint::new())
+    }
+}
+
+impl From<SignedMediocreBigint> for BigRational {
+    fn from(value: SignedMediocreBigint) -> Self {
+        BigRational {
+            numerator: value,
+            denominator: MediocreBigint::from(1u64),
+        }
+    }
+}
+
+impl From<MediocreBigint> for BigRational {
+    fn from(value: MediocreBigint) -> Self {
+        BigRational::from(SignedMediocreBigint::from(value))
+    }
+}
+
+impl From<u64> for BigRational {
+    fn from(value: u64) -> Self {
+        BigRational::from(MediocreBigint::from(value))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares `a/b` with `c/d` as `a * d` with `c * b`, which keeps the order since both
+/// denominators are positive
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let left = self.numerator.clone() * SignedMediocreBigint::from(other.denominator.clone());
+        let right = other.numerator.clone() * SignedMediocreBigint::from(self.denominator.clone());
+        left.cmp(&right)
+    }
+}
+
+impl std::ops::Neg for BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> Self::Output {
+        BigRational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl BigRational {
+    /// Adds `rhs` with its numerator negated when `subtract` is set
+    fn add_signed(&mut self, rhs: &BigRational, subtract: bool) {
+        let mut cross =
+            rhs.numerator.clone() * SignedMediocreBigint::from(self.denominator.clone());
+        if subtract {
+            cross = -cross;
+        }
+        self.numerator *= &SignedMediocreBigint::from(rhs.denominator.clone());
+        self.numerator += &cross;
+        self.denominator *= &rhs.denominator;
+        self.reduce();
+    }
+}
+
+impl std::ops::AddAssign<&BigRational> for BigRational {
+    fn add_assign(&mut self, rhs: &BigRational) {
+        self.add_signed(rhs, false);
+    }
+}
+
+impl std::ops::SubAssign<&BigRational> for BigRational {
+    fn sub_assign(&mut self, rhs: &BigRational) {
+        self.add_signed(rhs, true);
+    }
+}
+
+impl std::ops::MulAssign<&BigRational> for BigRational {
+    fn mul_assign(&mut self, rhs: &BigRational) {
+        self.numerator *= &rhs.numerator;
+        self.denominator *= &rhs.denominator;
+        self.reduce();
+    }
+}
+
+impl std::ops::DivAssign<&BigRational> for BigRational {
+    /// Panics when `rhs` is zero
+    fn div_assign(&mut self, rhs: &BigRational) {
+        if rhs.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        self.numerator *=
+            &SignedMediocreBigint::from_magnitude(rhs.is_negative(), rhs.denominator.clone());
+        self.denominator *= rhs.numerator.magnitude();
+        self.reduce();
+    }
+}
+
+/// The owned and borrowed forms of the rational operators, all delegating to the `&rhs`
+/// assignments above
+macro_rules! rational_operators {
+    ($($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {$(
+        impl std::ops::$op for BigRational {
+            type Output = BigRational;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                let mut lhs = self;
+                std::ops::$op_assign::$method_assign(&mut lhs, &rhs);
+                lhs
+            }
+        }
+
+        impl std::ops::$op<&BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $method(self, rhs: &BigRational) -> Self::Output {
+                let mut lhs = self.clone();
+                std::ops::$op_assign::$method_assign(&mut lhs, rhs);
+                lhs
+            }
+        }
+
+        impl std::ops::$op_assign for BigRational {
+            fn $method_assign(&mut self, rhs: Self) {
+                std::ops::$op_assign::$method_assign(self, &rhs);
+            }
+        }
+    )*};
+}
+rational_operators!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign
+);
+
+/// Always `p/q`, even when the denominator is 1, since that is what the judges ask for
+impl std::fmt::Display for BigRational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_random::next_random;
+
+    fn fraction(numerator: i64, denominator: u64) -> BigRational {
+        BigRational::from_parts(
+            SignedMediocreBigint::from_magnitude(
+                numerator < 0,
+                MediocreBigint::from(numerator.unsigned_abs()),
+            ),
+            MediocreBigint::from(denominator),
+        )
+    }
+
+    fn gcd(mut a: i128, mut b: i128) -> i128 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a.abs()
+    }
+
+    /// The reduced `numerator/denominator` string, checked with primitives
+    fn reduced(numerator: i128, denominator: i128) -> String {
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        format!("{}/{}", numerator / divisor, denominator / divisor)
+    }
+
+    #[test]
+    fn test_operators_match_i128() {
+        let mut state: u64 = 0x9b05688c2b3e6c1f;
+        let mut next = || next_random(&mut state);
+        for _ in 0..2000 {
+            let (a, b) = ((next() % 2001) as i128 - 1000, (next() % 1000 + 1) as i128);
+            let (c, d) = ((next() % 2001) as i128 - 1000, (next() % 1000 + 1) as i128);
+            let (x, y) = (fraction(a as i64, b as u64), fraction(c as i64, d as u64));
+            assert_eq!(x.to_string(), reduced(a, b));
+            assert_eq!((&x + &y).to_string(), reduced(a * d + c * b, b * d));
+            assert_eq!((&x - &y).to_string(), reduced(a * d - c * b, b * d));
+            assert_eq!((&x * &y).to_string(), reduced(a * c, b * d));
+            if c != 0 {
+                assert_eq!((&x / &y).to_string(), reduced(a * d, b * c));
+            }
+            assert_eq!(x.cmp(&y), (a * d).cmp(&(c * b)));
+        }
+    }
+
+    #[test]
+    fn test_reduction_and_signs() {
+        assert_eq!(fraction(6, 8), fraction(3, 4));
+        assert_eq!(fraction(0, 5), BigRational::new());
+        assert_eq!(fraction(0, 5).to_string(), "0/1");
+        assert_eq!(fraction(-4, 2).to_string(), "-2/1");
+        assert!(fraction(-4, 2).is_integer());
+        assert_eq!((-fraction(1, 3)).recip(), fraction(-3, 1));
+        assert_eq!(fraction(-1, 3).abs(), fraction(1, 3));
+        assert_eq!(fraction(1, 2) - fraction(1, 2), BigRational::new());
+        assert!(!(fraction(1, 2) - fraction(1, 2)).is_negative());
+        assert!(fraction(-1, 2) < fraction(-1, 3));
+        assert_eq!(
+            BigRational::from(7u64).denominator(),
+            &MediocreBigint::from(1u64)
+        );
+    }
+
+    #[test]
+    fn test_beyond_u64() {
+        // 50! / (25! * 25!) is C(50, 25), and the harmonic number H_60 has a 25 digit denominator
+        let ratio = BigRational::from(MediocreBigint::factorial(50))
+            / BigRational::from(MediocreBigint::factorial(25) * MediocreBigint::factorial(25));
+        assert_eq!(ratio, BigRational::from(MediocreBigint::binomial(50, 25)));
+
+        let harmonic: BigRational = (1..=60).fold(BigRational::new(), |sum, k| {
+            sum + BigRational::from(1u64) / BigRational::from(k)
+        });
+        assert_eq!(
+            harmonic.to_string(),
+            "15117092380124150817026911/3230237388259077233637600"
+        );
+        assert_eq!(
+            harmonic.to_decimal_string(30),
+            "4.679870412951737817188846811925"
+        );
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        assert_eq!(fraction(1, 3).to_decimal_string(5), "0.33333");
+        assert_eq!(fraction(2, 3).to_decimal_string(5), "0.66667");
+        assert_eq!(fraction(-2, 3).to_decimal_string(2), "-0.67");
+        assert_eq!(fraction(1, 8).to_decimal_string(2), "0.13");
+        assert_eq!(fraction(-1, 8).to_decimal_string(2), "-0.13");
+        assert_eq!(fraction(-1, 1000).to_decimal_string(2), "0.00");
+        assert_eq!(fraction(7, 2).to_decimal_string(0), "4");
+        assert_eq!(fraction(123, 1).to_decimal_string(3), "123.000");
+        assert_eq!(
+            fraction(1, 7).to_decimal_string(40),
+            "0.1428571428571428571428571428571428571429"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_divide_by_zero() {
+        let _ = fraction(1, 2) / BigRational::new();
+    }
+}
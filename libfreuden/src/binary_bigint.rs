@@ -0,0 +1,512 @@
+use crate::mediocre_bigint::MediocreBigint;
+
+/// BinaryBigint is a big unsigned integer in base 2^64 limbs, little-endian, for when the bits
+/// matter: shifts, masks, popcounts. Prefer `MediocreBigint` when decimal printing dominates,
+/// since printing a binary number is a quadratic conversion.
+/// There are never zero words on top, so zero has no words at all and the derived equality and
+/// hashing compare values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BinaryBigint {
+    words: Vec<u64>,
+}
+
+impl BinaryBigint {
+    pub fn new() -> BinaryBigint {
+        BinaryBigint::default()
+    }
+
+    /// Little-endian words, zero words on top are dropped
+    pub fn from_words(words: &[u64]) -> BinaryBigint {
+        let mut value = BinaryBigint {
+            words: words.to_vec(),
+        };
+        value.trim();
+        value
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The number of bits needed to write the value, zero for zero
+    pub fn bit_len(&self) -> u64 {
+        match self.words.last() {
+            Some(top) => self.words.len() as u64 * 64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    /// The index of the lowest set bit, `None` for zero which has no set bits
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        let index = self.words.iter().position(|&word| word != 0)?;
+        Some(index as u64 * 64 + self.words[index].trailing_zeros() as u64)
+    }
+
+    pub fn bit(&self, index: u64) -> bool {
+        let word = (index / 64) as usize;
+        word < self.words.len() && self.words[word] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set_bit(&mut self, index: u64, value: bool) {
+        let word = (index / 64) as usize;
+        if value {
+            if word >= self.words.len() {
+                self.words.resize(word + 1, 0);
+            }
+            self.words[word] |= 1 << (index % 64);
+        } else if word < self.words.len() {
+            self.words[word] &= !(1 << (index % 64));
+            self.trim();
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.words[..] {
+            [] => Some(0),
+            [low] => Some(low),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.words[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    /// Parses digits in `radix` from 2 to 36, going through `MediocreBigint`
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<BinaryBigint, std::num::ParseIntError> {
+        MediocreBigint::from_str_radix(text, radix).map(BinaryBigint::from)
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    /// Writes the words with `width` digits each below the top one, for hex and binary output
+    fn fmt_words(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        prefix: &str,
+        write_word: fn(&mut String, u64, usize) -> std::fmt::Result,
+        width: usize,
+    ) -> std::fmt::Result {
+        let mut digits = String::new();
+        match self.words.split_last() {
+            Some((&top, rest)) => {
+                write_word(&mut digits, top, 0)?;
+                for &word in rest.iter().rev() {
+                    write_word(&mut digits, word, width)?;
+                }
+            }
+            None => digits.push('0'),
+        }
+        f.pad_integral(true, prefix, &digits)
+    }
+}
+
+impl From<u64> for BinaryBigint {
+    fn from(value: u64) -> Self {
+        BinaryBigint::from_words(&[value])
+    }
+}
+
+impl From<u128> for BinaryBigint {
+    fn from(value: u128) -> Self {
+        BinaryBigint::from_words(&[value as u64, (value >> 64) as u64])
+    }
+}
+
+impl From<&MediocreBigint> for BinaryBigint {
+    fn from(value: &MediocreBigint) -> Self {
+        let words: Vec<u64> = value
+            .to_bytes_be()
+            .rchunks(8)
+            .map(|chunk| chunk.iter().fold(0, |word, &byte| word << 8 | byte as u64))
+            .collect();
+        BinaryBigint::from_words(&words)
+    }
+}
+
+impl From<MediocreBigint> for BinaryBigint {
+    fn from(value: MediocreBigint) -> Self {
+        BinaryBigint::from(&value)
+    }
+}
+
+impl From<&BinaryBigint> for MediocreBigint {
+    fn from(value: &BinaryBigint) -> Self {
+        let bytes: Vec<u8> = value
+            .words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        MediocreBigint::from_bytes_le(&bytes)
+    }
+}
+
+impl From<BinaryBigint> for MediocreBigint {
+    fn from(value: BinaryBigint) -> Self {
+        MediocreBigint::from(&value)
+    }
+}
+
+impl PartialOrd for BinaryBigint {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BinaryBigint {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.words
+            .len()
+            .cmp(&other.words.len())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+}
+
+impl std::ops::AddAssign<&BinaryBigint> for BinaryBigint {
+    fn add_assign(&mut self, rhs: &BinaryBigint) {
+        if self.words.len() < rhs.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        let mut carry = false;
+        for (i, word) in self.words.iter_mut().enumerate() {
+            let right = rhs.words.get(i).copied().unwrap_or(0);
+            if i >= rhs.words.len() && !carry {
+                break;
+            }
+            let (sum, overflow) = word.overflowing_add(right);
+            let (sum, carry_overflow) = sum.overflowing_add(carry as u64);
+            *word = sum;
+            carry = overflow || carry_overflow;
+        }
+        if carry {
+            self.words.push(1);
+        }
+    }
+}
+
+impl std::ops::SubAssign<&BinaryBigint> for BinaryBigint {
+    /// Panics when `rhs` is larger, like the unsigned primitives
+    fn sub_assign(&mut self, rhs: &BinaryBigint) {
+        if *self < *rhs {
+            panic!("attempt to subtract with overflow");
+        }
+        let mut borrow = false;
+        for (i, word) in self.words.iter_mut().enumerate() {
+            let right = rhs.words.get(i).copied().unwrap_or(0);
+            if i >= rhs.words.len() && !borrow {
+                break;
+            }
+            let (difference, overflow) = word.overflowing_sub(right);
+            let (difference, borrow_overflow) = difference.overflowing_sub(borrow as u64);
+            *word = difference;
+            borrow = overflow || borrow_overflow;
+        }
+        self.trim();
+    }
+}
+
+impl std::ops::MulAssign<&BinaryBigint> for BinaryBigint {
+    fn mul_assign(&mut self, rhs: &BinaryBigint) {
+        if self.is_zero() || rhs.is_zero() {
+            self.words.clear();
+            return;
+        }
+        let mut product = vec![0u64; self.words.len() + rhs.words.len()];
+        for (i, &left) in self.words.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, &right) in rhs.words.iter().enumerate() {
+                // At most (2^64 - 1)^2 + 2 * (2^64 - 1), which is exactly u128::MAX
+                let current = product[i + j] as u128 + left as u128 * right as u128 + carry;
+                product[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            product[i + rhs.words.len()] = carry as u64;
+        }
+        self.words = product;
+        self.trim();
+    }
+}
+
+impl std::ops::BitAndAssign<&BinaryBigint> for BinaryBigint {
+    fn bitand_assign(&mut self, rhs: &BinaryBigint) {
+        self.words.truncate(rhs.words.len());
+        for (word, right) in self.words.iter_mut().zip(&rhs.words) {
+            *word &= right;
+        }
+        self.trim();
+    }
+}
+
+impl std::ops::BitOrAssign<&BinaryBigint> for BinaryBigint {
+    fn bitor_assign(&mut self, rhs: &BinaryBigint) {
+        if self.words.len() < rhs.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (word, right) in self.words.iter_mut().zip(&rhs.words) {
+            *word |= right;
+        }
+    }
+}
+
+impl std::ops::BitXorAssign<&BinaryBigint> for BinaryBigint {
+    fn bitxor_assign(&mut self, rhs: &BinaryBigint) {
+        if self.words.len() < rhs.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (word, right) in self.words.iter_mut().zip(&rhs.words) {
+            *word ^= right;
+        }
+        self.trim();
+    }
+}
+
+/// The owned and borrowed forms of the binary operators, all delegating to the `&rhs`
+/// assignments above
+macro_rules! binary_operators {
+    ($($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {$(
+        impl std::ops::$op for BinaryBigint {
+            type Output = BinaryBigint;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                let mut lhs = self;
+                std::ops::$op_assign::$method_assign(&mut lhs, &rhs);
+                lhs
+            }
+        }
+
+        impl std::ops::$op<&BinaryBigint> for &BinaryBigint {
+            type Output = BinaryBigint;
+
+            fn $method(self, rhs: &BinaryBigint) -> Self::Output {
+                let mut lhs = self.clone();
+                std::ops::$op_assign::$method_assign(&mut lhs, rhs);
+                lhs
+            }
+        }
+
+        impl std::ops::$op_assign for BinaryBigint {
+            fn $method_assign(&mut self, rhs: Self) {
+                std::ops::$op_assign::$method_assign(self, &rhs);
+            }
+        }
+    )*};
+}
+binary_operators!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    BitAnd bitand BitAndAssign bitand_assign,
+    BitOr bitor BitOrAssign bitor_assign,
+    BitXor bitxor BitXorAssign bitxor_assign
+);
+
+impl std::ops::ShlAssign<u64> for BinaryBigint {
+    fn shl_assign(&mut self, bits: u64) {
+        if self.is_zero() {
+            return;
+        }
+        let (words, bits) = ((bits / 64) as usize, (bits % 64) as u32);
+        if bits > 0 {
+            let mut carry = 0;
+            for word in self.words.iter_mut() {
+                (*word, carry) = (*word << bits | carry, *word >> (64 - bits));
+            }
+            if carry > 0 {
+                self.words.push(carry);
+            }
+        }
+        self.words.splice(0..0, std::iter::repeat_n(0, words));
+    }
+}
+
+impl std::ops::ShrAssign<u64> for BinaryBigint {
+    fn shr_assign(&mut self, bits: u64) {
+        let (words, bits) = ((bits / 64) as usize, (bits % 64) as u32);
+        if words >= self.words.len() {
+            self.words.clear();
+            return;
+        }
+        self.words.drain(..words);
+        if bits > 0 {
+            let mut carry = 0;
+            for word in self.words.iter_mut().rev() {
+                (*word, carry) = (*word >> bits | carry, *word << (64 - bits));
+            }
+        }
+        self.trim();
+    }
+}
+
+impl std::ops::Shl<u64> for BinaryBigint {
+    type Output = BinaryBigint;
+
+    fn shl(mut self, bits: u64) -> Self::Output {
+        self <<= bits;
+        self
+    }
+}
+
+impl std::ops::Shr<u64> for BinaryBigint {
+    type Output = BinaryBigint;
+
+    fn shr(mut self, bits: u64) -> Self::Output {
+        self >>= bits;
+        self
+    }
+}
+
+/// Decimal output converts to `MediocreBigint` first, which is quadratic in the length
+impl std::fmt::Display for BinaryBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        MediocreBigint::from(self).fmt(f)
+    }
+}
+
+impl std::fmt::LowerHex for BinaryBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        self.fmt_words(
+            f,
+            "0x",
+            |out, word, width| write!(out, "{:0width$x}", word),
+            16,
+        )
+    }
+}
+
+impl std::fmt::Binary for BinaryBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        self.fmt_words(
+            f,
+            "0b",
+            |out, word, width| write!(out, "{:0width$b}", word),
+            64,
+        )
+    }
+}
+
+impl std::str::FromStr for BinaryBigint {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BinaryBigint::from_str_radix(s, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_random::{next_random, random_u128};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_operators_match_u128() {
+        let mut state = 0x510e527fade682d1;
+        for _ in 0..3000 {
+            let (a, b) = (random_u128(&mut state), random_u128(&mut state));
+            let (x, y) = (BinaryBigint::from(a), BinaryBigint::from(b));
+            assert_eq!((&x & &y).to_u128(), Some(a & b));
+            assert_eq!((&x | &y).to_u128(), Some(a | b));
+            assert_eq!((&x ^ &y).to_u128(), Some(a ^ b));
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            if let Some(sum) = a.checked_add(b) {
+                assert_eq!((&x + &y).to_u128(), Some(sum));
+            }
+            if a >= b {
+                assert_eq!((&x - &y).to_u128(), Some(a - b));
+            }
+            if let Some(product) = a.checked_mul(b) {
+                assert_eq!((&x * &y).to_u128(), Some(product));
+            }
+            let shift = next_random(&mut state) % 128;
+            assert_eq!((x.clone() >> shift).to_u128(), Some(a >> shift));
+            if a.leading_zeros() as u64 >= shift {
+                assert_eq!((x.clone() << shift).to_u128(), Some(a << shift));
+            }
+            assert_eq!(x.count_ones(), a.count_ones() as u64);
+            assert_eq!(x.bit_len(), 128 - a.leading_zeros() as u64);
+            assert_eq!(
+                x.trailing_zeros(),
+                (a != 0).then_some(a.trailing_zeros() as u64)
+            );
+            assert_eq!(x.bit(shift), a >> shift & 1 == 1);
+            assert_eq!(format!("{:x} {:#b}", x, x), format!("{:x} {:#b}", a, a));
+        }
+    }
+
+    #[test]
+    fn test_bits_beyond_u128() {
+        let mut value = BinaryBigint::from(1u64) << 1000;
+        assert_eq!(value.bit_len(), 1001);
+        assert_eq!(value.trailing_zeros(), Some(1000));
+        assert_eq!(value.words().len(), 16);
+        value.set_bit(3, true);
+        assert_eq!(value.count_ones(), 2);
+        value.set_bit(1000, false);
+        assert_eq!(value, BinaryBigint::from(8u64));
+        assert_eq!(
+            BinaryBigint::from(u128::MAX) + BinaryBigint::from(1u64),
+            BinaryBigint::from(1u64) << 128
+        );
+        assert_eq!(
+            (BinaryBigint::from(5u64) << 300) >> 299,
+            BinaryBigint::from(10u64)
+        );
+        assert_eq!(BinaryBigint::from(5u64) >> 3, BinaryBigint::new());
+        assert_eq!(BinaryBigint::new().trailing_zeros(), None);
+        assert_eq!(BinaryBigint::from_words(&[7, 0, 0]).words(), [7]);
+        assert_eq!(
+            format!("{:x}", BinaryBigint::from(1u64) << 64),
+            format!("1{}", "0".repeat(16))
+        );
+        assert_eq!(
+            format!("{:>6x}|{:#x}", BinaryBigint::new(), BinaryBigint::new()),
+            "     0|0x0"
+        );
+    }
+
+    #[test]
+    fn test_mediocre_conversions() {
+        let decimal = MediocreBigint::from(7u64).pow(400);
+        let binary = BinaryBigint::from(&decimal);
+        assert_eq!(MediocreBigint::from(&binary), decimal);
+        assert_eq!(binary.to_string(), decimal.to_string());
+        assert_eq!(
+            BinaryBigint::from_str(&decimal.to_string()).unwrap(),
+            binary
+        );
+        assert_eq!(format!("{:x}", binary), decimal.to_str_radix(16));
+
+        let popcount = BinaryBigint::from_str_radix(&"10".repeat(200), 2).unwrap();
+        assert_eq!(popcount.count_ones(), 200);
+        assert_eq!(
+            BinaryBigint::from(MediocreBigint::new()),
+            BinaryBigint::new()
+        );
+        assert_eq!(MediocreBigint::from(BinaryBigint::new()).to_string(), "0");
+        assert!(BinaryBigint::from_str("12a").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_underflow() {
+        let _ = BinaryBigint::from(1u64) - BinaryBigint::from(2u64);
+    }
+}
@@ -2,8 +2,13 @@
 #[cfg(feature = "unstable")]
 extern crate test;
 
-// TODO: Implement tests in their relevant submodules
-
+pub mod big_rational;
+pub mod binary_bigint;
 pub mod fenwick_tree;
 pub mod input;
 pub mod mediocre_bigint;
+pub mod ntt;
+pub mod output;
+
+#[cfg(test)]
+mod test_random;
@@ -9,10 +9,9 @@ const LIMIT_POWER: u32 = 17;
 const LIMIT_BASE: MediocreCarrySize = 10;
 pub const BIGINT_LIMIT: MediocreCarrySize = MediocreCarrySize::pow(LIMIT_BASE, LIMIT_POWER);
 
-#[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct MediocreBigint {
-    // TODO: Correct the setnja test usage of this so that I can make this not public (and anagramcounting)
-    pub digits: Vec<MediocreDigitSize>,
+    digits: Vec<MediocreDigitSize>,
 }
 
 impl MediocreBigint {
@@ -21,6 +20,74 @@ impl MediocreBigint {
         MediocreBigint { digits: segments }
     }
 
+    /// Little-endian limbs in base `BIGINT_LIMIT`. Limbs at or above the base are carries
+    /// that have not been normalized out yet, which is allowed.
+    pub fn from_limbs(limbs: &[MediocreDigitSize]) -> MediocreBigint {
+        MediocreBigint {
+            digits: limbs.to_vec(),
+        }
+    }
+
+    /// The limbs as stored, see `from_limbs`
+    pub fn limbs(&self) -> &[MediocreDigitSize] {
+        &self.digits
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.iter().all(|&limb| limb == 0)
+    }
+
+    /// The number of decimal digits, where zero has one digit like its `to_string()`
+    pub fn digit_len(&self) -> usize {
+        let limbs = self.normalized_limbs();
+        match limbs.last() {
+            Some(top) => (limbs.len() - 1) * LIMIT_POWER as usize + top.ilog10() as usize + 1,
+            None => 1,
+        }
+    }
+
+    /// The number of bits needed to write the value in binary, zero for zero
+    pub fn bit_len(&self) -> u64 {
+        let binary = self.to_binary_limbs();
+        match binary.last() {
+            Some(top) => (binary.len() as u64 - 1) * 64 + (64 - top.leading_zeros() as u64),
+            None => 0,
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|value| u64::try_from(value).ok())
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        self.normalized_limbs()
+            .iter()
+            .rev()
+            .try_fold(0u128, |value, &limb| {
+                value
+                    .checked_mul(BIGINT_LIMIT as u128)?
+                    .checked_add(limb as u128)
+            })
+    }
+
+    /// The value in base 2^64, little-endian and without zero limbs on top
+    fn to_binary_limbs(&self) -> Vec<u64> {
+        let mut binary: Vec<u64> = Vec::new();
+        for &limb in self.normalized_limbs().iter().rev() {
+            // binary = binary * BIGINT_LIMIT + limb
+            let mut carry = limb as u128;
+            for word in binary.iter_mut() {
+                let current = *word as u128 * BIGINT_LIMIT as u128 + carry;
+                *word = current as u64;
+                carry = current >> 64;
+            }
+            if carry > 0 {
+                binary.push(carry as u64);
+            }
+        }
+        binary
+    }
+
     #[allow(unused)]
     pub fn is_normalized(&self) -> bool {
         self.digits
@@ -29,13 +96,17 @@ impl MediocreBigint {
     }
 
     pub fn normalize(&mut self) {
-        let leftover = self.digits.iter_mut().fold(0, |carry, x| {
-            let original = *x as MediocreCarrySize;
-            let carry_sum: MediocreCarrySize = original + carry as MediocreCarrySize;
-            let (div, modulo) = (carry_sum / BIGINT_LIMIT, carry_sum % BIGINT_LIMIT);
+        // In u128, as a limb close to u64::MAX can still receive a carry
+        let leftover = self.digits.iter_mut().fold(0u128, |carry, x| {
+            let carry_sum = *x as u128 + carry;
+            let (div, modulo) = (
+                carry_sum / BIGINT_LIMIT as u128,
+                carry_sum % BIGINT_LIMIT as u128,
+            );
             *x = modulo as MediocreDigitSize;
-            div as MediocreDigitSize
+            div
         });
+        let leftover = leftover as MediocreCarrySize;
         let (div, modulo) = (
             leftover / BIGINT_LIMIT as MediocreDigitSize,
             leftover % BIGINT_LIMIT as MediocreDigitSize,
@@ -49,6 +120,29 @@ impl MediocreBigint {
     }
 }
 
+impl From<u64> for MediocreBigint {
+    fn from(value: u64) -> Self {
+        MediocreBigint::from(value as u128)
+    }
+}
+
+impl From<usize> for MediocreBigint {
+    fn from(value: usize) -> Self {
+        MediocreBigint::from(value as u128)
+    }
+}
+
+impl From<u128> for MediocreBigint {
+    fn from(mut value: u128) -> Self {
+        let mut digits = Vec::new();
+        while value > 0 {
+            digits.push((value % BIGINT_LIMIT as u128) as MediocreDigitSize);
+            value /= BIGINT_LIMIT as u128;
+        }
+        MediocreBigint { digits }
+    }
+}
+
 impl std::ops::Add for MediocreBigint {
     type Output = MediocreBigint;
 
@@ -68,32 +162,110 @@ impl std::ops::AddAssign for MediocreBigint {
 
 impl std::ops::AddAssign<&MediocreBigint> for MediocreBigint {
     fn add_assign(&mut self, rhs: &MediocreBigint) {
-        let overlap = std::cmp::min(self.digits.len(), rhs.digits.len());
-        let mut left_iter = self.digits[..overlap].iter_mut();
-        let mut right_iter = rhs.digits[..overlap].iter();
+        if self.digits.len() < rhs.digits.len() {
+            self.digits.resize(rhs.digits.len(), 0);
+        }
         let mut carry: MediocreCarrySize = 0;
+        for (i, left) in self.digits.iter_mut().enumerate() {
+            if i >= rhs.digits.len() && carry == 0 {
+                break;
+            }
+            let right = rhs.digits.get(i).copied().unwrap_or(0);
+            // Splitting both limbs first keeps pending carries close to u64::MAX from overflowing
+            let current = *left % BIGINT_LIMIT + right % BIGINT_LIMIT + carry;
+            carry = *left / BIGINT_LIMIT + right / BIGINT_LIMIT + current / BIGINT_LIMIT;
+            *left = (current % BIGINT_LIMIT) as MediocreDigitSize;
+        }
+        if carry > 0 {
+            self.digits.push(carry as MediocreDigitSize);
+        }
+    }
+}
 
-        for (left, right) in left_iter.zip(right_iter) {
-            // Handle when we have both values
-            carry += *left as MediocreCarrySize + *right as MediocreCarrySize;
-            (carry, *left) = (
-                carry / BIGINT_LIMIT,
-                (carry % BIGINT_LIMIT) as MediocreDigitSize,
-            );
+impl MediocreBigint {
+    /// The limbs without pending carries or zero limbs on top, only copied when a carry is
+    /// actually pending
+    fn normalized_limbs(&self) -> std::borrow::Cow<'_, [MediocreDigitSize]> {
+        if self.is_normalized() {
+            let length = self
+                .digits
+                .iter()
+                .rposition(|&limb| limb != 0)
+                .map_or(0, |i| i + 1);
+            std::borrow::Cow::Borrowed(&self.digits[..length])
+        } else {
+            let mut normalized = self.clone();
+            normalized.normalize();
+            trim_zero_limbs(&mut normalized.digits);
+            std::borrow::Cow::Owned(normalized.digits)
         }
+    }
 
-        left_iter = self.digits[overlap..].iter_mut();
-        right_iter = rhs.digits[overlap..].iter();
+    /// Compares the values, whatever carries are still pending in either
+    fn cmp_magnitude(&self, other: &MediocreBigint) -> std::cmp::Ordering {
+        let (left, right) = (self.normalized_limbs(), other.normalized_limbs());
+        left.len()
+            .cmp(&right.len())
+            .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+    }
+}
 
-        if let Some(left) = left_iter.next() {
-            *left += carry as MediocreDigitSize;
-        } else if let Some(right) = right_iter.next() {
-            self.digits.push(*right + carry as MediocreDigitSize);
-            self.digits
-                .append(right_iter.copied().collect::<Vec<_>>().as_mut());
-        } else if carry > 0 {
-            self.digits.push(carry as MediocreDigitSize);
+/// Equality, ordering and hashing go by numeric value, so `[]`, `[0]` and limbs with pending
+/// carries compare equal to their normalized form
+impl PartialEq for MediocreBigint {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized_limbs() == other.normalized_limbs()
+    }
+}
+
+impl Eq for MediocreBigint {}
+
+impl PartialOrd for MediocreBigint {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MediocreBigint {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.cmp_magnitude(other)
+    }
+}
+
+impl std::hash::Hash for MediocreBigint {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.normalized_limbs().hash(state);
+    }
+}
+
+impl std::ops::Sub for MediocreBigint {
+    type Output = MediocreBigint;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut lhs = self;
+        lhs -= &rhs;
+        lhs
+    }
+}
+
+impl std::ops::SubAssign for MediocreBigint {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.sub_assign(&rhs);
+    }
+}
+
+impl std::ops::SubAssign<&MediocreBigint> for MediocreBigint {
+    /// Panics when `rhs` is larger, like the unsigned primitives
+    fn sub_assign(&mut self, rhs: &MediocreBigint) {
+        if self.cmp_magnitude(rhs) == std::cmp::Ordering::Less {
+            panic!("attempt to subtract with overflow");
         }
+        let mut rhs = rhs.clone();
+        rhs.normalize();
+        self.normalize();
+
+        sub_limbs_in_place(&mut self.digits, &rhs.digits);
+        trim_zero_limbs(&mut self.digits);
     }
 }
 
@@ -116,97 +288,327 @@ impl std::ops::MulAssign for MediocreBigint {
 
 impl std::ops::MulAssign<&MediocreBigint> for MediocreBigint {
     fn mul_assign(&mut self, rhs: &MediocreBigint) {
-        // Handle empty cases
-        if self.digits.is_empty() || rhs.digits.is_empty() {
+        let rhs_limbs = rhs.normalized_limbs();
+        if self.is_zero() || rhs_limbs.is_empty() {
             self.digits.clear();
             return;
         }
 
         // Special case for single digit multiplication to avoid unnecessary allocations
-        if rhs.digits.len() == 1 && rhs.digits[0] < BIGINT_LIMIT as MediocreDigitSize {
-            let r_digit = rhs.digits[0];
-            if r_digit == 0 {
-                self.digits.clear();
-                return;
-            }
-            if r_digit == 1 {
-                return; // Identity
-            }
+        if rhs_limbs.len() == 1 {
+            self.mul_assign_small(rhs_limbs[0]);
+            return;
+        }
 
-            let mut carry = MediocreCarrySize::default();
-            // Multiply in place
-            for digit in &mut self.digits {
-                let product =
-                    (*digit as MediocreCarrySize) * (r_digit as MediocreCarrySize) + carry;
-                let (div, modulo) = (product / BIGINT_LIMIT, product % BIGINT_LIMIT);
-                *digit = modulo as MediocreDigitSize;
-                carry = div;
-            }
+        let mut product = mul_limbs(&self.normalized_limbs(), &rhs_limbs);
+        trim_zero_limbs(&mut product);
+        self.digits = product;
+    }
+}
 
-            // Handle any remaining carry
-            while carry > 0 {
-                let (div, modulo) = (carry / BIGINT_LIMIT, carry % BIGINT_LIMIT);
-                self.digits.push(modulo as MediocreDigitSize);
-                carry = div;
-            }
-            return;
+impl MediocreBigint {
+    /// Multiplies in place by a nonzero factor, pending carries in self included
+    fn mul_assign_small(&mut self, factor: u64) {
+        if factor == 1 {
+            return; // Identity
+        }
+        let base = BIGINT_LIMIT as u128;
+        let mut carry: u128 = 0;
+        for digit in &mut self.digits {
+            // A denormalized limb times a large factor leaves no room to add the carry first
+            let product = *digit as u128 * factor as u128;
+            let low = product % base + carry;
+            *digit = (low % base) as MediocreDigitSize;
+            carry = product / base + low / base;
+        }
+
+        // Handle any remaining carry
+        while carry > 0 {
+            self.digits
+                .push((carry % BIGINT_LIMIT as u128) as MediocreDigitSize);
+            carry /= BIGINT_LIMIT as u128;
+        }
+    }
+}
+
+/// Below this many limbs in the shorter operand, Karatsuba's extra additions cost more than the
+/// multiplications it saves
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// From this many limbs in the shorter operand on, the NTT beats Karatsuba
+const NTT_THRESHOLD: usize = 1536;
+
+/// NTT multiplication splits every limb in two below this, so the convolutions stay exact
+const LIMB_SPLIT: MediocreDigitSize = 1_000_000_000;
+
+/// The product of normalized limbs, exactly `a.len() + b.len()` limbs long
+fn mul_limbs(a: &[MediocreDigitSize], b: &[MediocreDigitSize]) -> Vec<MediocreDigitSize> {
+    let shorter = a.len().min(b.len());
+    if shorter < KARATSUBA_THRESHOLD {
+        mul_schoolbook(a, b)
+    } else if shorter < NTT_THRESHOLD {
+        mul_karatsuba(a, b)
+    } else {
+        mul_ntt(a, b)
+    }
+}
+
+/// Multiplication through `ntt::convolution_exact`. Each limb is written as
+/// `high * LIMB_SPLIT + low`, and the three convolutions of the parts are combined like
+/// Karatsuba's, which keeps every coefficient below 2^31 before multiplying.
+fn mul_ntt(a: &[MediocreDigitSize], b: &[MediocreDigitSize]) -> Vec<MediocreDigitSize> {
+    let split = |limbs: &[MediocreDigitSize]| -> (Vec<u64>, Vec<u64>, Vec<u64>) {
+        let high: Vec<_> = limbs.iter().map(|limb| limb / LIMB_SPLIT).collect();
+        let low: Vec<_> = limbs.iter().map(|limb| limb % LIMB_SPLIT).collect();
+        let sum = high.iter().zip(&low).map(|(h, l)| h + l).collect();
+        (high, low, sum)
+    };
+    let (a_high, a_low, a_sum) = split(a);
+    let (b_high, b_low, b_sum) = split(b);
+    let high = crate::ntt::convolution_exact(&a_high, &b_high);
+    let low = crate::ntt::convolution_exact(&a_low, &b_low);
+    let sum = crate::ntt::convolution_exact(&a_sum, &b_sum);
+
+    // Term k is high[k] * LIMB_SPLIT^2 + middle[k] * LIMB_SPLIT + low[k], where
+    // LIMB_SPLIT^2 is BIGINT_LIMIT * 10 and so carries high[k] into limb k + 1
+    let high_scale = (LIMB_SPLIT as u128).pow(2) / BIGINT_LIMIT as u128;
+    let mut product = vec![0; a.len() + b.len()];
+    let mut carry: u128 = 0;
+    for (k, limb) in product.iter_mut().enumerate() {
+        let mut current = carry;
+        if k < low.len() {
+            let middle = sum[k] - high[k] - low[k];
+            current += low[k] + middle * LIMB_SPLIT as u128;
+        }
+        if k > 0 && k - 1 < high.len() {
+            current += high[k - 1] * high_scale;
+        }
+        *limb = (current % BIGINT_LIMIT as u128) as MediocreDigitSize;
+        carry = current / BIGINT_LIMIT as u128;
+    }
+    product
+}
+
+/// Schoolbook multiplication into a single accumulator, carrying once per product limb
+fn mul_schoolbook(a: &[MediocreDigitSize], b: &[MediocreDigitSize]) -> Vec<MediocreDigitSize> {
+    let base = BIGINT_LIMIT as u128;
+    let mut product = vec![0; a.len() + b.len()];
+    for (i, &left) in a.iter().enumerate() {
+        if left == 0 {
+            continue;
+        }
+        let mut carry: u128 = 0;
+        for (j, &right) in b.iter().enumerate() {
+            // Below base^2, so a u128 never overflows here
+            let current = product[i + j] as u128 + left as u128 * right as u128 + carry;
+            product[i + j] = (current % base) as MediocreDigitSize;
+            carry = current / base;
+        }
+        product[i + b.len()] = carry as MediocreDigitSize;
+    }
+    product
+}
+
+/// Karatsuba multiplication, falling back to `mul_limbs` for the three half-sized products. An
+/// operand much shorter than the other is handled by multiplying it with slices of the longer one.
+fn mul_karatsuba(a: &[MediocreDigitSize], b: &[MediocreDigitSize]) -> Vec<MediocreDigitSize> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let half = a.len() / 2;
+    let mut product = vec![0; a.len() + b.len()];
+
+    if b.len() <= half {
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            let mut partial = mul_limbs(chunk, b);
+            trim_zero_limbs(&mut partial);
+            add_limbs_at(&mut product, &partial, i * b.len());
         }
+        return product;
+    }
+
+    let (a_low, a_high) = a.split_at(half);
+    let (b_low, b_high) = b.split_at(half);
+    let mut low = mul_limbs(a_low, b_low);
+    let mut high = mul_limbs(a_high, b_high);
+    trim_zero_limbs(&mut low);
+    trim_zero_limbs(&mut high);
+
+    // (a_low + a_high)(b_low + b_high) - low - high is the middle term
+    let mut middle = mul_limbs(&sum_halves(a_low, a_high), &sum_halves(b_low, b_high));
+    sub_limbs_in_place(&mut middle, &low);
+    sub_limbs_in_place(&mut middle, &high);
+    trim_zero_limbs(&mut middle);
+
+    add_limbs_at(&mut product, &low, 0);
+    add_limbs_at(&mut product, &middle, half);
+    add_limbs_at(&mut product, &high, 2 * half);
+    product
+}
+
+/// The sum of two halves of an operand, with room for the carry out of the longer one
+fn sum_halves(low: &[MediocreDigitSize], high: &[MediocreDigitSize]) -> Vec<MediocreDigitSize> {
+    let mut sum = vec![0; low.len().max(high.len()) + 1];
+    add_limbs_at(&mut sum, low, 0);
+    add_limbs_at(&mut sum, high, 0);
+    sum
+}
 
-        // For general case, we need a separate result vector
-        // Store original digits and clear self to reuse it
-        let original_digits = std::mem::take(&mut self.digits);
-        let original_self = MediocreBigint {
-            digits: original_digits,
+/// Adds normalized limbs into `target` starting at limb `offset`. The sum has to fit in `target`.
+fn add_limbs_at(target: &mut [MediocreDigitSize], addend: &[MediocreDigitSize], offset: usize) {
+    let mut carry = 0;
+    for (i, target_limb) in target[offset..].iter_mut().enumerate() {
+        if i >= addend.len() && carry == 0 {
+            break;
+        }
+        let sum = *target_limb + addend.get(i).copied().unwrap_or(0) + carry;
+        (*target_limb, carry) = if sum >= BIGINT_LIMIT {
+            (sum - BIGINT_LIMIT, 1)
+        } else {
+            (sum, 0)
         };
+    }
+}
 
-        // Pre-allocate result with enough capacity for the worst case
-        let mut result = MediocreBigint::new();
-        result
-            .digits
-            .reserve(original_self.digits.len() + rhs.digits.len() + 1);
+/// Subtracts normalized limbs from `left` in place. `right` must not be larger than `left`.
+fn sub_limbs_in_place(left: &mut [MediocreDigitSize], right: &[MediocreDigitSize]) {
+    let mut borrow = 0;
+    for (i, left_limb) in left.iter_mut().enumerate() {
+        if i >= right.len() && borrow == 0 {
+            break;
+        }
+        let right_limb = right.get(i).copied().unwrap_or(0) + borrow;
+        if *left_limb >= right_limb {
+            *left_limb -= right_limb;
+            borrow = 0;
+        } else {
+            *left_limb = *left_limb + BIGINT_LIMIT - right_limb;
+            borrow = 1;
+        }
+    }
+}
 
-        // For each digit in rhs, multiply it with all digits in original_self
-        for (i, &r_digit) in rhs.digits.iter().enumerate() {
-            // Skip multiplication by zero
-            if r_digit == 0 {
-                continue;
-            }
+impl MediocreBigint {
+    /// The quotient and remainder of dividing by `rhs`, rounding the quotient down
+    pub fn div_rem(&self, rhs: &MediocreBigint) -> (MediocreBigint, MediocreBigint) {
+        let mut divisor = rhs.clone();
+        divisor.normalize();
+        trim_zero_limbs(&mut divisor.digits);
+        if divisor.digits.is_empty() {
+            panic!("attempt to divide by zero");
+        }
+        let mut dividend = self.clone();
+        dividend.normalize();
+        trim_zero_limbs(&mut dividend.digits);
+
+        let (quotient, remainder) = if dividend.digits.len() < divisor.digits.len() {
+            (Vec::new(), dividend.digits)
+        } else if divisor.digits.len() == 1 {
+            let (quotient, remainder) = div_rem_limb(&dividend.digits, divisor.digits[0]);
+            (quotient, vec![remainder])
+        } else {
+            div_rem_knuth(&dividend.digits, &divisor.digits)
+        };
+        let mut quotient = MediocreBigint { digits: quotient };
+        let mut remainder = MediocreBigint { digits: remainder };
+        trim_zero_limbs(&mut quotient.digits);
+        trim_zero_limbs(&mut remainder.digits);
+        (quotient, remainder)
+    }
+}
+
+fn trim_zero_limbs(limbs: &mut Vec<MediocreDigitSize>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
 
-            let mut partial_result = Vec::with_capacity(i + original_self.digits.len() + 2);
+/// Divides normalized limbs by a single limb, from the most significant limb down
+fn div_rem_limb(
+    limbs: &[MediocreDigitSize],
+    divisor: MediocreDigitSize,
+) -> (Vec<MediocreDigitSize>, MediocreDigitSize) {
+    let base = BIGINT_LIMIT as u128;
+    let divisor = divisor as u128;
+    let mut remainder: u128 = 0;
+    let mut quotient = vec![0; limbs.len()];
+    for i in (0..limbs.len()).rev() {
+        let current = remainder * base + limbs[i] as u128;
+        quotient[i] = (current / divisor) as MediocreDigitSize;
+        remainder = current % divisor;
+    }
+    (quotient, remainder as MediocreDigitSize)
+}
 
-            // Add zeros for the shift based on position
-            partial_result.extend(std::iter::repeat(0).take(i));
+/// Multiplies normalized limbs by a single limb, returning one limb more than it was given
+fn mul_limb(limbs: &[MediocreDigitSize], factor: MediocreDigitSize) -> Vec<MediocreDigitSize> {
+    let mut carry: u128 = 0;
+    let mut product = Vec::with_capacity(limbs.len() + 1);
+    for &limb in limbs {
+        carry += limb as u128 * factor as u128;
+        product.push((carry % BIGINT_LIMIT as u128) as MediocreDigitSize);
+        carry /= BIGINT_LIMIT as u128;
+    }
+    product.push(carry as MediocreDigitSize);
+    product
+}
 
-            let mut carry = MediocreCarrySize::default();
+/// Knuth's algorithm D (TAOCP vol. 2, 4.3.1) in base `BIGINT_LIMIT`, for normalized limbs
+/// without leading zero limbs where the divisor has at least two limbs and is not longer than
+/// the dividend
+fn div_rem_knuth(
+    dividend: &[MediocreDigitSize],
+    divisor: &[MediocreDigitSize],
+) -> (Vec<MediocreDigitSize>, Vec<MediocreDigitSize>) {
+    let base = BIGINT_LIMIT as u128;
+    let n = divisor.len();
+    let m = dividend.len() - n;
 
-            // Multiply each digit of original_self by the current digit of rhs
-            for &l_digit in original_self.digits.iter() {
-                let product =
-                    (l_digit as MediocreCarrySize) * (r_digit as MediocreCarrySize) + carry;
-                let (div, modulo) = (product / BIGINT_LIMIT, product % BIGINT_LIMIT);
-                partial_result.push(modulo as MediocreDigitSize);
-                carry = div;
-            }
+    // Scale both sides so the top divisor limb is at least base / 2, which keeps every
+    // quotient digit estimate at most two too large
+    let scale = BIGINT_LIMIT / (divisor[n - 1] + 1);
+    let mut u = mul_limb(dividend, scale);
+    let v = mul_limb(divisor, scale);
+    let (v_top, v_next) = (v[n - 1] as u128, v[n - 2] as u128);
 
-            // Handle any remaining carry
-            while carry > 0 {
-                let (div, modulo) = (carry / BIGINT_LIMIT, carry % BIGINT_LIMIT);
-                partial_result.push(modulo as MediocreDigitSize);
-                carry = div;
+    let mut quotient = vec![0; m + 1];
+    for j in (0..=m).rev() {
+        // Estimate the quotient digit from the top two limbs and refine it with the third
+        let numerator = u[j + n] as u128 * base + u[j + n - 1] as u128;
+        let mut estimate = numerator / v_top;
+        let mut remainder = numerator % v_top;
+        while estimate >= base || estimate * v_next > remainder * base + u[j + n - 2] as u128 {
+            estimate -= 1;
+            remainder += v_top;
+            if remainder >= base {
+                break;
             }
+        }
 
-            // Create partial_bigint without cloning
-            let partial_bigint = MediocreBigint {
-                digits: partial_result,
-            };
-
-            // Add this partial result to the total
-            result += partial_bigint;
+        // Subtract estimate * v from the current window of u
+        let mut carry: u128 = 0;
+        let mut borrow: i128 = 0;
+        for i in 0..=n {
+            let product = estimate * *v.get(i).unwrap_or(&0) as u128 + carry;
+            carry = product / base;
+            let difference = u[i + j] as i128 - (product % base) as i128 - borrow;
+            borrow = (difference < 0) as i128;
+            u[i + j] = (difference + borrow * base as i128) as MediocreDigitSize;
         }
 
-        // Move the result back into self
-        self.digits = result.digits;
+        // The estimate was still one too large, so add v back once
+        if borrow != 0 {
+            estimate -= 1;
+            let mut carry: u128 = 0;
+            for i in 0..=n {
+                let sum = u[i + j] as u128 + *v.get(i).unwrap_or(&0) as u128 + carry;
+                u[i + j] = (sum % base) as MediocreDigitSize;
+                carry = sum / base;
+            }
+        }
+        quotient[j] = estimate as MediocreDigitSize;
     }
+
+    let (remainder, _) = div_rem_limb(&u[..n], scale);
+    (quotient, remainder)
 }
 
 impl std::ops::Div for MediocreBigint {
@@ -226,95 +628,1931 @@ impl std::ops::DivAssign for MediocreBigint {
 
 impl std::ops::DivAssign<&MediocreBigint> for MediocreBigint {
     fn div_assign(&mut self, rhs: &MediocreBigint) {
-        // Zero divisor check (consider empty or all-zero as zero)
-        let is_rhs_zero = rhs.digits.is_empty() || rhs.digits.iter().all(|&d| d == 0);
-        if is_rhs_zero {
-            panic!("attempt to divide by zero");
+        *self = self.div_rem(rhs).0;
+    }
+}
+
+impl std::ops::Rem for MediocreBigint {
+    type Output = MediocreBigint;
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
+    }
+}
+
+impl std::ops::RemAssign for MediocreBigint {
+    fn rem_assign(&mut self, rhs: Self) {
+        self.rem_assign(&rhs);
+    }
+}
+
+impl std::ops::RemAssign<&MediocreBigint> for MediocreBigint {
+    fn rem_assign(&mut self, rhs: &MediocreBigint) {
+        *self = self.div_rem(rhs).1;
+    }
+}
+
+impl MediocreBigint {
+    /// `self` to the power `exponent` by repeated squaring, where `0^0` is 1
+    pub fn pow(&self, mut exponent: u32) -> MediocreBigint {
+        let mut result = MediocreBigint::from(1u64);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base *= &base.clone();
+            }
         }
+        result
+    }
 
-        // If self is zero, result is zero
-        let is_self_zero = self.digits.is_empty() || self.digits.iter().all(|&d| d == 0);
-        if is_self_zero {
-            self.digits.clear();
-            return;
+    /// `self` to the power `exponent`, mod `modulus`. Panics when `modulus` is zero.
+    pub fn pow_mod(&self, exponent: &MediocreBigint, modulus: &MediocreBigint) -> MediocreBigint {
+        let mut result = MediocreBigint::from(1u64).div_rem(modulus).1;
+        let mut base = self.div_rem(modulus).1;
+        for word in exponent.to_binary_limbs() {
+            for bit in 0..64 {
+                if word >> bit & 1 == 1 {
+                    result = (result * base.clone()).div_rem(modulus).1;
+                }
+                base = (base.clone() * base).div_rem(modulus).1;
+            }
         }
+        result
+    }
+
+    /// The largest integer whose square is at most `self`
+    pub fn isqrt(&self) -> MediocreBigint {
+        self.nth_root(2)
+    }
 
-        // Optimize single-limb divisor
-        if rhs.digits.len() == 1 {
-            let divisor = rhs.digits[0] as u128;
-            if divisor == 1 {
-                return; // identity
+    /// The largest integer whose `n`th power is at most `self`. Panics when `n` is zero.
+    pub fn nth_root(&self, n: u32) -> MediocreBigint {
+        assert!(n > 0, "the zeroth root is not defined");
+        if n == 1 || self.is_zero() {
+            let mut root = self.clone();
+            root.normalize();
+            return root;
+        }
+        // Newton's method from a power of two above the root only ever decreases
+        let mut root = MediocreBigint::from(2u64).pow(self.bit_len().div_ceil(n as u64) as u32);
+        loop {
+            let next =
+                (root.clone() * (n as u64 - 1) + self.div_rem(&root.pow(n - 1)).0) / n as u64;
+            if next >= root {
+                return root;
             }
+            root = next;
+        }
+    }
 
-            let base = BIGINT_LIMIT as u128;
-            let mut rem: u128 = 0;
+    /// The greatest common divisor, where `gcd(0, 0)` is 0
+    pub fn gcd(&self, other: &MediocreBigint) -> MediocreBigint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            a %= &b;
+            std::mem::swap(&mut a, &mut b);
+        }
+        a.normalize();
+        trim_zero_limbs(&mut a.digits);
+        a
+    }
 
-            // Prepare quotient vector with same length
-            let n = self.digits.len();
-            let mut q: Vec<MediocreDigitSize> = vec![0; n];
+    /// The least common multiple, which is 0 when either side is
+    pub fn lcm(&self, other: &MediocreBigint) -> MediocreBigint {
+        if self.is_zero() || other.is_zero() {
+            return MediocreBigint::new();
+        }
+        self.div_rem(&self.gcd(other)).0 * other.clone()
+    }
 
-            // Process from most significant limb to least
-            for i in (0..n).rev() {
-                let cur = rem * base + self.digits[i] as u128;
-                let qdigit = cur / divisor;
-                rem = cur % divisor;
-                q[i] = qdigit as MediocreDigitSize;
-            }
+    /// `n!`, multiplied as a balanced product tree so the large multiplications stay even
+    pub fn factorial(n: u64) -> MediocreBigint {
+        range_product(1, n)
+    }
 
-            // Trim leading zeros (highest limbs)
-            while q.last().copied() == Some(0) {
-                q.pop();
-            }
+    /// The binomial coefficient `n` choose `k`, 0 when `k > n`
+    pub fn binomial(n: u64, k: u64) -> MediocreBigint {
+        if k > n {
+            return MediocreBigint::new();
+        }
+        let k = k.min(n - k);
+        if k == 0 {
+            return MediocreBigint::from(1u64);
+        }
+        range_product(n - k + 1, n)
+            .div_rem(&MediocreBigint::factorial(k))
+            .0
+    }
+}
 
-            self.digits = q;
-            return;
+/// The product of the integers in `low..=high`, 1 when it is empty. The bounds are inclusive so
+/// that nothing has to compute `u64::MAX + 1`.
+fn range_product(low: u64, high: u64) -> MediocreBigint {
+    if low > high {
+        return MediocreBigint::from(1u64);
+    }
+    if high - low < 16 {
+        let mut product = MediocreBigint::from(1u64);
+        for factor in low..=high {
+            product *= factor;
         }
+        return product;
+    }
+    let middle = low + (high - low) / 2;
+    range_product(low, middle) * range_product(middle + 1, high)
+}
+
+impl std::ops::Add<u64> for MediocreBigint {
+    type Output = MediocreBigint;
 
-        // TODO: multi-limb divisor not yet implemented for this mediocre bigint
-        // Implementing full Knuth division is beyond current needs of this project.
-        unimplemented!("Division by multi-limb MediocreBigint is not implemented");
+    fn add(mut self, rhs: u64) -> Self::Output {
+        self += rhs;
+        self
     }
 }
 
-impl std::fmt::Display for MediocreBigint {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut display = self.clone();
-        display.normalize();
-        // Read digit by digit and write out char by char
-        if display.digits.len() > 0 {
-            let mut string = String::new();
-            for digit in display.digits {
-                let flipped = digit.to_string().chars().rev().collect::<String>();
-                let fmtted = format!("{:0<width$}", flipped, width = (LIMIT_POWER) as usize);
-                let padded = fmtted;
-                string.push_str(&padded);
-            }
-            string
-                .trim_end_matches('0')
-                .chars()
-                .rev()
-                .collect::<String>()
-                .fmt(f)
-        } else {
-            0u8.fmt(f)
+impl std::ops::AddAssign<u64> for MediocreBigint {
+    fn add_assign(&mut self, rhs: u64) {
+        let mut carry = rhs as u128;
+        for digit in &mut self.digits {
+            if carry == 0 {
+                return;
+            }
+            let sum = *digit as u128 + carry;
+            *digit = (sum % BIGINT_LIMIT as u128) as MediocreDigitSize;
+            carry = sum / BIGINT_LIMIT as u128;
+        }
+        while carry > 0 {
+            self.digits
+                .push((carry % BIGINT_LIMIT as u128) as MediocreDigitSize);
+            carry /= BIGINT_LIMIT as u128;
         }
     }
 }
 
-impl std::str::FromStr for MediocreBigint {
-    type Err = std::num::ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Read char by char and write out digit by digit
-        let mut segments = if s.len() > 0 {
-            Vec::with_capacity(s.len())
+impl std::ops::Mul<u64> for MediocreBigint {
+    type Output = MediocreBigint;
+
+    fn mul(mut self, rhs: u64) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl std::ops::MulAssign<u64> for MediocreBigint {
+    fn mul_assign(&mut self, rhs: u64) {
+        if rhs == 0 {
+            self.digits.clear();
         } else {
-            Vec::new()
-        };
-        for chunk in s.as_bytes().rchunks(LIMIT_POWER as usize) {
-            let substr = String::from_utf8_lossy(chunk);
-            let digit = substr.parse()?;
-            segments.push(digit);
+            self.mul_assign_small(rhs);
         }
-        Ok(MediocreBigint { digits: segments })
+    }
+}
+
+impl std::ops::Div<u64> for MediocreBigint {
+    type Output = MediocreBigint;
+
+    fn div(mut self, rhs: u64) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl std::ops::DivAssign<u64> for MediocreBigint {
+    fn div_assign(&mut self, rhs: u64) {
+        if rhs == 0 {
+            panic!("attempt to divide by zero");
+        }
+        self.normalize();
+        let (mut quotient, _) = div_rem_limb(&self.digits, rhs);
+        trim_zero_limbs(&mut quotient);
+        self.digits = quotient;
+    }
+}
+
+/// The remainder is a u64 again, computed without allocating
+impl std::ops::Rem<u64> for &MediocreBigint {
+    type Output = u64;
+
+    fn rem(self, rhs: u64) -> u64 {
+        if rhs == 0 {
+            panic!("attempt to calculate the remainder with a divisor of zero");
+        }
+        rem_limb(&self.normalized_limbs(), rhs)
+    }
+}
+
+/// The remainder of normalized limbs divided by a single u64, from the most significant limb down
+fn rem_limb(limbs: &[MediocreDigitSize], divisor: u64) -> u64 {
+    limbs.iter().rev().fold(0u128, |remainder, &limb| {
+        (remainder * BIGINT_LIMIT as u128 + limb as u128) % divisor as u128
+    }) as u64
+}
+
+impl std::ops::Rem<u64> for MediocreBigint {
+    type Output = u64;
+
+    fn rem(self, rhs: u64) -> u64 {
+        &self % rhs
+    }
+}
+
+impl std::iter::Sum for MediocreBigint {
+    fn sum<I: Iterator<Item = MediocreBigint>>(iter: I) -> Self {
+        iter.fold(MediocreBigint::new(), |mut total, value| {
+            total += &value;
+            total
+        })
+    }
+}
+
+impl<'a> std::iter::Sum<&'a MediocreBigint> for MediocreBigint {
+    fn sum<I: Iterator<Item = &'a MediocreBigint>>(iter: I) -> Self {
+        iter.fold(MediocreBigint::new(), |mut total, value| {
+            total += value;
+            total
+        })
+    }
+}
+
+impl std::iter::Product for MediocreBigint {
+    fn product<I: Iterator<Item = MediocreBigint>>(iter: I) -> Self {
+        iter.fold(MediocreBigint::from(1u64), |mut total, value| {
+            total *= &value;
+            total
+        })
+    }
+}
+
+impl<'a> std::iter::Product<&'a MediocreBigint> for MediocreBigint {
+    fn product<I: Iterator<Item = &'a MediocreBigint>>(iter: I) -> Self {
+        iter.fold(MediocreBigint::from(1u64), |mut total, value| {
+            total *= value;
+            total
+        })
+    }
+}
+
+impl MediocreBigint {
+    /// Writes the decimal digits limb by limb, zero-padding all but the top one
+    fn write_decimal<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let limbs = self.normalized_limbs();
+        match limbs.split_last() {
+            Some((top, rest)) => {
+                write!(out, "{}", top)?;
+                for limb in rest.iter().rev() {
+                    write!(out, "{:0width$}", limb, width = LIMIT_POWER as usize)?;
+                }
+                Ok(())
+            }
+            None => out.write_char('0'),
+        }
+    }
+
+    /// Formats through `pad_integral` only when width, fill or sign flags ask for it, so plain
+    /// `{}` writes straight into the formatter without building a string first
+    fn fmt_decimal(&self, nonnegative: bool, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.width().is_none() && !f.sign_plus() {
+            if !nonnegative {
+                f.write_str("-")?;
+            }
+            return self.write_decimal(f);
+        }
+        let mut digits = String::with_capacity(self.digits.len() * LIMIT_POWER as usize + 1);
+        self.write_decimal(&mut digits)?;
+        f.pad_integral(nonnegative, "", &digits)
+    }
+
+    /// The digits in `radix` from 2 to 36, with lowercase letters above 9
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix {} is not in 2..=36",
+            radix
+        );
+        if radix == 10 {
+            return self.to_string();
+        }
+        let (chunk, chunk_digits) = radix_chunk(radix);
+        let mut limbs = self.normalized_limbs().into_owned();
+        // Least significant first, a chunk of digits per division of the whole number
+        let mut digits = Vec::new();
+        while !limbs.is_empty() {
+            let (quotient, mut remainder) = div_rem_limb(&limbs, chunk);
+            limbs = quotient;
+            trim_zero_limbs(&mut limbs);
+            for _ in 0..chunk_digits {
+                if limbs.is_empty() && remainder == 0 {
+                    break;
+                }
+                digits
+                    .push(std::char::from_digit((remainder % radix as u64) as u32, radix).unwrap());
+                remainder /= radix as u64;
+            }
+        }
+        if digits.is_empty() {
+            return String::from("0");
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// Parses digits in `radix` from 2 to 36, either case, with an optional leading `+`
+    pub fn from_str_radix(
+        text: &str,
+        radix: u32,
+    ) -> Result<MediocreBigint, std::num::ParseIntError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix {} is not in 2..=36",
+            radix
+        );
+        let digits = text.strip_prefix('+').unwrap_or(text);
+        // Errors come from the primitive parser, which has the only constructor for them
+        if digits.is_empty() {
+            return Err(u64::from_str_radix(text, radix).unwrap_err());
+        }
+        if !digits.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+            return Err(u64::from_str_radix("+", radix).unwrap_err());
+        }
+        let (_, chunk_digits) = radix_chunk(radix);
+        let mut value = MediocreBigint::new();
+        let mut rest = digits;
+        while !rest.is_empty() {
+            let length = match rest.len() % chunk_digits as usize {
+                0 => chunk_digits as usize,
+                partial => partial,
+            };
+            let (chunk, remaining) = rest.split_at(length);
+            value *= (radix as u64).pow(length as u32);
+            value += u64::from_str_radix(chunk, radix)?;
+            rest = remaining;
+        }
+        value.normalize();
+        Ok(value)
+    }
+
+    /// The value as big-endian bytes without leading zeros, `[0]` for zero
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let bytes: Vec<u8> = self
+            .to_binary_limbs()
+            .iter()
+            .rev()
+            .flat_map(|word| word.to_be_bytes())
+            .skip_while(|&byte| byte == 0)
+            .collect();
+        if bytes.is_empty() {
+            return vec![0];
+        }
+        bytes
+    }
+
+    /// The value of little-endian bytes
+    pub fn from_bytes_le(bytes: &[u8]) -> MediocreBigint {
+        // 2^56 is below BIGINT_LIMIT, so seven bytes at a time multiply by a single limb
+        let mut value = MediocreBigint::new();
+        for chunk in bytes.rchunks(7) {
+            let chunk_value = chunk
+                .iter()
+                .rev()
+                .fold(0u64, |value, &byte| value << 8 | byte as u64);
+            value *= 1u64 << (8 * chunk.len());
+            value += chunk_value;
+        }
+        value.normalize();
+        value
+    }
+}
+
+/// The largest power of `radix` below `BIGINT_LIMIT` and its exponent, so it fits in one limb
+fn radix_chunk(radix: u32) -> (MediocreDigitSize, u32) {
+    let mut chunk = radix as MediocreDigitSize;
+    let mut exponent = 1;
+    while chunk * (radix as MediocreDigitSize) < BIGINT_LIMIT {
+        chunk *= radix as MediocreDigitSize;
+        exponent += 1;
+    }
+    (chunk, exponent)
+}
+
+impl std::fmt::Display for MediocreBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_decimal(true, f)
+    }
+}
+
+impl std::fmt::LowerHex for MediocreBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl std::fmt::Binary for MediocreBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0b", &self.to_str_radix(2))
+    }
+}
+
+impl std::str::FromStr for MediocreBigint {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Each chunk's parser would accept a `+` of its own, so only one is allowed up front
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return MediocreBigint::from_str_radix(s, 10);
+        }
+        // Read char by char and write out digit by digit
+        let mut segments = Vec::with_capacity(digits.len());
+        for chunk in digits.as_bytes().rchunks(LIMIT_POWER as usize) {
+            let substr = String::from_utf8_lossy(chunk);
+            let digit = substr.parse()?;
+            segments.push(digit);
+        }
+        Ok(MediocreBigint { digits: segments })
+    }
+}
+
+/// A signed big integer as a sign and a `MediocreBigint` magnitude. Zero is never negative.
+/// Division truncates towards zero and the remainder takes the sign of the dividend, like the
+/// primitive integers.
+#[derive(Debug, Clone, Default)]
+pub struct SignedMediocreBigint {
+    negative: bool,
+    magnitude: MediocreBigint,
+}
+
+impl SignedMediocreBigint {
+    pub fn new() -> SignedMediocreBigint {
+        SignedMediocreBigint::default()
+    }
+
+    pub fn from_magnitude(negative: bool, magnitude: MediocreBigint) -> SignedMediocreBigint {
+        let mut value = SignedMediocreBigint {
+            negative,
+            magnitude,
+        };
+        value.fix_zero_sign();
+        value
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn magnitude(&self) -> &MediocreBigint {
+        &self.magnitude
+    }
+
+    pub fn abs(&self) -> SignedMediocreBigint {
+        SignedMediocreBigint::from_magnitude(false, self.magnitude.clone())
+    }
+
+    fn fix_zero_sign(&mut self) {
+        if self.magnitude.digits.iter().all(|&limb| limb == 0) {
+            self.negative = false;
+        }
+    }
+
+    /// Adds `magnitude` with the given sign to `self`
+    fn add_signed(&mut self, negative: bool, magnitude: &MediocreBigint) {
+        if self.negative == negative {
+            self.magnitude += magnitude;
+        } else if self.magnitude.cmp_magnitude(magnitude) != std::cmp::Ordering::Less {
+            self.magnitude -= magnitude;
+        } else {
+            self.magnitude = magnitude.clone() - std::mem::take(&mut self.magnitude);
+            self.negative = negative;
+        }
+        self.fix_zero_sign();
+    }
+}
+
+impl From<MediocreBigint> for SignedMediocreBigint {
+    fn from(magnitude: MediocreBigint) -> Self {
+        SignedMediocreBigint::from_magnitude(false, magnitude)
+    }
+}
+
+impl PartialEq for SignedMediocreBigint {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for SignedMediocreBigint {}
+
+impl std::hash::Hash for SignedMediocreBigint {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.negative.hash(state);
+        self.magnitude.hash(state);
+    }
+}
+
+impl PartialOrd for SignedMediocreBigint {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SignedMediocreBigint {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => std::cmp::Ordering::Greater,
+            (true, false) => std::cmp::Ordering::Less,
+            (false, false) => self.magnitude.cmp_magnitude(&other.magnitude),
+            (true, true) => other.magnitude.cmp_magnitude(&self.magnitude),
+        }
+    }
+}
+
+impl std::ops::Neg for SignedMediocreBigint {
+    type Output = SignedMediocreBigint;
+
+    fn neg(self) -> Self::Output {
+        SignedMediocreBigint::from_magnitude(!self.negative, self.magnitude)
+    }
+}
+
+impl std::ops::AddAssign<&SignedMediocreBigint> for SignedMediocreBigint {
+    fn add_assign(&mut self, rhs: &SignedMediocreBigint) {
+        self.add_signed(rhs.negative, &rhs.magnitude);
+    }
+}
+
+impl std::ops::SubAssign<&SignedMediocreBigint> for SignedMediocreBigint {
+    fn sub_assign(&mut self, rhs: &SignedMediocreBigint) {
+        self.add_signed(!rhs.negative, &rhs.magnitude);
+    }
+}
+
+impl std::ops::MulAssign<&SignedMediocreBigint> for SignedMediocreBigint {
+    fn mul_assign(&mut self, rhs: &SignedMediocreBigint) {
+        self.magnitude *= &rhs.magnitude;
+        self.negative = self.negative != rhs.negative;
+        self.fix_zero_sign();
+    }
+}
+
+impl std::ops::DivAssign<&SignedMediocreBigint> for SignedMediocreBigint {
+    fn div_assign(&mut self, rhs: &SignedMediocreBigint) {
+        let (quotient, _) = self.magnitude.div_rem(&rhs.magnitude);
+        *self = SignedMediocreBigint::from_magnitude(self.negative != rhs.negative, quotient);
+    }
+}
+
+impl std::ops::RemAssign<&SignedMediocreBigint> for SignedMediocreBigint {
+    fn rem_assign(&mut self, rhs: &SignedMediocreBigint) {
+        let (_, remainder) = self.magnitude.div_rem(&rhs.magnitude);
+        *self = SignedMediocreBigint::from_magnitude(self.negative, remainder);
+    }
+}
+
+/// The owned forms of the signed operators, all delegating to the `&rhs` assignments above
+macro_rules! signed_operators {
+    ($($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {$(
+        impl std::ops::$op for SignedMediocreBigint {
+            type Output = SignedMediocreBigint;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                let mut lhs = self;
+                std::ops::$op_assign::$method_assign(&mut lhs, &rhs);
+                lhs
+            }
+        }
+
+        impl std::ops::$op_assign for SignedMediocreBigint {
+            fn $method_assign(&mut self, rhs: Self) {
+                std::ops::$op_assign::$method_assign(self, &rhs);
+            }
+        }
+    )*};
+}
+signed_operators!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+);
+
+impl std::fmt::Display for SignedMediocreBigint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.magnitude.fmt_decimal(!self.negative, f)
+    }
+}
+
+impl std::str::FromStr for SignedMediocreBigint {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        // The magnitude parser takes a `+` of its own, which would let "-+5" through
+        if digits.starts_with(['+', '-']) {
+            return Err("-".parse::<u64>().unwrap_err());
+        }
+        Ok(SignedMediocreBigint::from_magnitude(
+            negative,
+            MediocreBigint::from_str_radix(digits, 10)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_random::{next_random, random_u128};
+    use std::str::FromStr;
+
+    fn big(text: &str) -> MediocreBigint {
+        MediocreBigint::from_str(text).unwrap()
+    }
+
+    /// A limb that is often at one of the edges the quotient estimate has to handle
+    fn random_limb(state: &mut u64) -> MediocreDigitSize {
+        match next_random(state) % 5 {
+            0 => 0,
+            1 => BIGINT_LIMIT - 1,
+            2 => BIGINT_LIMIT / 2,
+            _ => next_random(state) % BIGINT_LIMIT,
+        }
+    }
+
+    /// Schoolbook product of normalized limbs, kept independent of `MulAssign`
+    fn reference_product(a: &[MediocreDigitSize], b: &[MediocreDigitSize]) -> Vec<u128> {
+        let mut product = vec![0u128; a.len() + b.len() + 1];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &y) in b.iter().enumerate() {
+                let current = product[i + j] + x as u128 * y as u128 + carry;
+                product[i + j] = current % BIGINT_LIMIT as u128;
+                carry = current / BIGINT_LIMIT as u128;
+            }
+            product[i + b.len()] += carry;
+        }
+        product
+    }
+
+    #[test]
+    fn test_div_rem_matches_u128() {
+        let mut state = 0x9e3779b97f4a7c15;
+        for _ in 0..2000 {
+            let a = (next_random(&mut state) as u128) << 64 | next_random(&mut state) as u128;
+            let b = match next_random(&mut state) % 3 {
+                0 => next_random(&mut state) as u128 % 1000 + 1,
+                1 => next_random(&mut state) as u128 + 1,
+                _ => a >> (next_random(&mut state) % 64) | 1,
+            };
+            let (quotient, remainder) = big(&a.to_string()).div_rem(&big(&b.to_string()));
+            assert_eq!(quotient.to_string(), (a / b).to_string(), "{} / {}", a, b);
+            assert_eq!(remainder.to_string(), (a % b).to_string(), "{} % {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_multi_limb() {
+        let mut state = 12345;
+        for _ in 0..500 {
+            let divisor_len = 2 + next_random(&mut state) as usize % 6;
+            let quotient_len = 1 + next_random(&mut state) as usize % 6;
+            let mut divisor: Vec<_> = (0..divisor_len).map(|_| random_limb(&mut state)).collect();
+            *divisor.last_mut().unwrap() = divisor_len as u64 + random_limb(&mut state) % 1000;
+            let quotient: Vec<_> = (0..quotient_len).map(|_| random_limb(&mut state)).collect();
+            let remainder: Vec<_> = (0..divisor_len)
+                .map(|i| match i + 1 == divisor_len {
+                    true => next_random(&mut state) % divisor[i],
+                    false => random_limb(&mut state),
+                })
+                .collect();
+
+            // dividend = quotient * divisor + remainder
+            let mut dividend = reference_product(&quotient, &divisor);
+            let mut carry = 0u128;
+            for (i, limb) in dividend.iter_mut().enumerate() {
+                let current = *limb + *remainder.get(i).unwrap_or(&0) as u128 + carry;
+                *limb = current % BIGINT_LIMIT as u128;
+                carry = current / BIGINT_LIMIT as u128;
+            }
+            let dividend = MediocreBigint {
+                digits: dividend.into_iter().map(|limb| limb as u64).collect(),
+            };
+
+            let (mut expected_quotient, mut expected_remainder) = (
+                MediocreBigint { digits: quotient },
+                MediocreBigint { digits: remainder },
+            );
+            trim_zero_limbs(&mut expected_quotient.digits);
+            trim_zero_limbs(&mut expected_remainder.digits);
+            let divisor = MediocreBigint { digits: divisor };
+            assert_eq!(
+                dividend.div_rem(&divisor),
+                (expected_quotient.clone(), expected_remainder.clone()),
+                "{} / {}",
+                dividend,
+                divisor
+            );
+            assert_eq!(dividend.clone() / divisor.clone(), expected_quotient);
+            assert_eq!(dividend % divisor, expected_remainder);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_edge_cases() {
+        let factorial_30 = big("265252859812191058636308480000000");
+        let factorial_25 = big("15511210043330985984000000");
+        assert_eq!(
+            (factorial_30.clone() / factorial_25.clone()).to_string(),
+            "17100720"
+        );
+        assert_eq!((factorial_25.clone() % factorial_30.clone()), factorial_25);
+
+        // Denormalized limbs still divide by their value
+        let denormalized = MediocreBigint {
+            digits: vec![BIGINT_LIMIT + 4, 0, 0],
+        };
+        let (quotient, remainder) = denormalized.div_rem(&big("200000000000000002"));
+        assert_eq!(quotient.to_string(), "0");
+        assert_eq!(remainder.to_string(), "100000000000000004");
+        let mut quotient = denormalized.clone();
+        quotient /= &MediocreBigint::from_limbs(&[1]);
+        assert!(quotient.is_normalized());
+        assert_eq!(quotient, denormalized);
+        let mut quotient = denormalized.clone();
+        quotient /= &MediocreBigint::from_limbs(&[4, 0]);
+        assert_eq!(quotient.to_string(), "25000000000000001");
+
+        let mut value = big("1000000000000000000000000000000000000");
+        value %= big("99999999999999999999");
+        assert_eq!(value.to_string(), "10000000000000000");
+        let mut value = big("1000000000000000000000000000000000000");
+        value /= big("99999999999999999999");
+        assert_eq!(value.to_string(), "10000000000000000");
+    }
+
+    #[test]
+    fn test_mul_matches_reference() {
+        let mut state = 0xdeadbeef;
+        for round in 0..300 {
+            // Mostly short operands, with some on both sides of the Karatsuba threshold
+            let max_len = if round % 10 == 0 { 200 } else { 40 };
+            let a_len = 1 + next_random(&mut state) as usize % max_len;
+            let b_len = 1 + next_random(&mut state) as usize % max_len;
+            let a: Vec<_> = (0..a_len).map(|_| random_limb(&mut state)).collect();
+            let b: Vec<_> = (0..b_len).map(|_| random_limb(&mut state)).collect();
+
+            let mut expected = MediocreBigint {
+                digits: reference_product(&a, &b)
+                    .into_iter()
+                    .map(|limb| limb as MediocreDigitSize)
+                    .collect(),
+            };
+            trim_zero_limbs(&mut expected.digits);
+            let product = MediocreBigint::from_limbs(&a) * MediocreBigint::from_limbs(&b);
+            assert!(product.is_normalized());
+            assert_eq!(product.limbs(), expected.limbs(), "{:?} * {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_mul_ntt_matches_schoolbook() {
+        let mut state = 0x1f83d9abfb41bd6b;
+        for (a_len, b_len) in [(64, 64), (100, 350), (513, 77)] {
+            let a: Vec<_> = (0..a_len).map(|_| random_limb(&mut state)).collect();
+            let b: Vec<_> = (0..b_len).map(|_| random_limb(&mut state)).collect();
+            assert_eq!(
+                mul_ntt(&a, &b),
+                mul_schoolbook(&a, &b),
+                "{} x {}",
+                a_len,
+                b_len
+            );
+        }
+        let nines = vec![BIGINT_LIMIT - 1; 200];
+        assert_eq!(mul_ntt(&nines, &nines), mul_schoolbook(&nines, &nines));
+
+        // Past the threshold, through the operator and Karatsuba's halves
+        let a: Vec<_> = (0..NTT_THRESHOLD * 3)
+            .map(|_| random_limb(&mut state))
+            .collect();
+        let b: Vec<_> = (0..NTT_THRESHOLD + 5)
+            .map(|_| random_limb(&mut state))
+            .collect();
+        let mut expected = mul_karatsuba(&a, &b);
+        trim_zero_limbs(&mut expected);
+        let product = MediocreBigint::from_limbs(&a) * MediocreBigint::from_limbs(&b);
+        assert_eq!(product.limbs(), expected);
+    }
+
+    #[test]
+    fn test_pow_and_pow_mod() {
+        assert_eq!(big("0").pow(0), big("1"));
+        assert_eq!(big("5").pow(0), big("1"));
+        assert_eq!(big("2").pow(127).to_u128(), Some(1 << 127));
+        assert_eq!(
+            big("3").pow(200),
+            (0..200).fold(big("1"), |power, _| power * big("3"))
+        );
+        assert_eq!(
+            big("10").pow(40).to_string(),
+            format!("1{}", "0".repeat(40))
+        );
+
+        let mut state = 0x6a09e667f3bcc909;
+        for _ in 0..200 {
+            let (base, exponent) = (next_random(&mut state), next_random(&mut state) % 100_000);
+            let modulus = next_random(&mut state) % 1_000_000_000 + 1;
+            let mut expected = 1 % modulus as u128;
+            let mut square = base as u128 % modulus as u128;
+            let mut remaining = exponent;
+            while remaining > 0 {
+                if remaining & 1 == 1 {
+                    expected = expected * square % modulus as u128;
+                }
+                square = square * square % modulus as u128;
+                remaining >>= 1;
+            }
+            let result = MediocreBigint::from(base).pow_mod(
+                &MediocreBigint::from(exponent),
+                &MediocreBigint::from(modulus),
+            );
+            assert_eq!(
+                result.to_u128(),
+                Some(expected),
+                "{}^{} % {}",
+                base,
+                exponent,
+                modulus
+            );
+        }
+        // Fermat's little theorem for the Mersenne prime 2^127 - 1
+        let prime = big("2").pow(127) - big("1");
+        let exponent = prime.clone() - big("1");
+        assert_eq!(big("3").pow_mod(&exponent, &prime), big("1"));
+        assert_eq!(big("3").pow_mod(&big("0"), &big("1")), big("0"));
+    }
+
+    #[test]
+    fn test_roots() {
+        for value in (0u128..2000).chain([u64::MAX as u128, u128::MAX, 10u128.pow(34)]) {
+            let root = MediocreBigint::from(value).isqrt().to_u128().unwrap();
+            assert!(
+                root * root <= value && (root + 1).checked_mul(root + 1).is_none_or(|s| s > value)
+            );
+        }
+        let huge = big("123456789").pow(50);
+        assert_eq!(huge.nth_root(50), big("123456789"));
+        assert_eq!((huge.clone() - big("1")).nth_root(50), big("123456788"));
+        assert_eq!(huge.nth_root(25), big("123456789").pow(2));
+        assert_eq!(big("1000").nth_root(3), big("10"));
+        assert_eq!(big("999").nth_root(3), big("9"));
+        assert_eq!(big("17").nth_root(1), big("17"));
+        assert_eq!(big("17").nth_root(100), big("1"));
+        assert_eq!(big("0").nth_root(4), big("0"));
+    }
+
+    #[test]
+    fn test_gcd_lcm_factorial_binomial() {
+        assert_eq!(big("0").gcd(&big("0")), big("0"));
+        assert_eq!(big("0").gcd(&big("12")), big("12"));
+        assert_eq!(big("12").gcd(&big("18")), big("6"));
+        assert_eq!(big("12").lcm(&big("18")), big("36"));
+        assert_eq!(big("0").lcm(&big("18")), big("0"));
+        let a = big("2").pow(100) * big("3").pow(40);
+        let b = big("2").pow(70) * big("5").pow(30) * big("3");
+        assert_eq!(a.gcd(&b), big("2").pow(70) * big("3"));
+        assert_eq!(
+            a.lcm(&b),
+            big("2").pow(100) * big("3").pow(40) * big("5").pow(30)
+        );
+
+        assert_eq!(MediocreBigint::factorial(0), big("1"));
+        assert_eq!(
+            MediocreBigint::factorial(25).to_string(),
+            "15511210043330985984000000"
+        );
+        let factorial_100 = (1..=100u64).fold(big("1"), |product, factor| {
+            product * MediocreBigint::from(factor)
+        });
+        assert_eq!(MediocreBigint::factorial(100), factorial_100);
+
+        assert_eq!(MediocreBigint::binomial(5, 2), big("10"));
+        assert_eq!(MediocreBigint::binomial(5, 6), big("0"));
+        assert_eq!(MediocreBigint::binomial(0, 0), big("1"));
+        assert_eq!(
+            MediocreBigint::binomial(67, 33).to_u128(),
+            Some(14226520737620288370)
+        );
+        assert_eq!(
+            MediocreBigint::binomial(100, 50).to_string(),
+            "100891344545564193334812497256"
+        );
+
+        // Bounds at u64::MAX must not step past it
+        let max = u64::MAX as u128;
+        assert_eq!(MediocreBigint::binomial(u64::MAX, 0), big("1"));
+        assert_eq!(MediocreBigint::binomial(u64::MAX, 1).to_u128(), Some(max));
+        assert_eq!(
+            MediocreBigint::binomial(u64::MAX, 2).to_u128(),
+            Some(max * (max - 1) / 2)
+        );
+        assert_eq!(
+            MediocreBigint::binomial(u64::MAX, u64::MAX - 1).to_u128(),
+            Some(max)
+        );
+        assert_eq!(MediocreBigint::binomial(u64::MAX, u64::MAX), big("1"));
+    }
+
+    /// `value` in `radix` through the primitive, for comparing against
+    fn u128_radix(mut value: u128, radix: u32) -> String {
+        let mut digits = Vec::new();
+        loop {
+            digits.push(std::char::from_digit((value % radix as u128) as u32, radix).unwrap());
+            value /= radix as u128;
+            if value == 0 {
+                return digits.iter().rev().collect();
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_conversion_matches_u128() {
+        let mut state = 0xbb67ae8584caa73b;
+        let values = (0..300)
+            .map(|_| (next_random(&mut state) as u128) << 64 | next_random(&mut state) as u128);
+        for value in values.chain([0, 1, u128::MAX]) {
+            for radix in 2..=36 {
+                let text = u128_radix(value, radix);
+                let bigint = MediocreBigint::from(value);
+                assert_eq!(
+                    bigint.to_str_radix(radix),
+                    text,
+                    "{} in radix {}",
+                    value,
+                    radix
+                );
+                let parsed = MediocreBigint::from_str_radix(&text.to_uppercase(), radix).unwrap();
+                assert_eq!(parsed.to_u128(), Some(value));
+            }
+            let bigint = MediocreBigint::from(value);
+            let bytes = value.to_be_bytes();
+            let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(15);
+            assert_eq!(bigint.to_bytes_be(), bytes[start..]);
+            assert_eq!(MediocreBigint::from_bytes_le(&value.to_le_bytes()), bigint);
+        }
+
+        let huge = big("3").pow(5000);
+        let hex = huge.to_str_radix(16);
+        assert_eq!(MediocreBigint::from_str_radix(&hex, 16).unwrap(), huge);
+        let mut bytes = huge.to_bytes_be();
+        assert_eq!(bytes.len(), hex.len().div_ceil(2));
+        bytes.reverse();
+        assert_eq!(MediocreBigint::from_bytes_le(&bytes), huge);
+        assert_eq!(MediocreBigint::from_bytes_le(&[]), big("0"));
+        assert_eq!(big("0").to_bytes_be(), [0]);
+    }
+
+    #[test]
+    fn test_from_str_radix_errors() {
+        use std::num::IntErrorKind;
+        let kind = |text: &str, radix| {
+            *MediocreBigint::from_str_radix(text, radix)
+                .unwrap_err()
+                .kind()
+        };
+        assert_eq!(kind("", 10), IntErrorKind::Empty);
+        assert_eq!(kind("+", 16), IntErrorKind::InvalidDigit);
+        assert_eq!(kind("102", 2), IntErrorKind::InvalidDigit);
+        assert_eq!(kind("12+3", 10), IntErrorKind::InvalidDigit);
+        assert_eq!(kind("-5", 10), IntErrorKind::InvalidDigit);
+        assert_eq!(kind("ü", 36), IntErrorKind::InvalidDigit);
+        assert_eq!(
+            kind(&format!("{}g", "f".repeat(40)), 16),
+            IntErrorKind::InvalidDigit
+        );
+        assert_eq!(
+            MediocreBigint::from_str_radix("+zz", 36).unwrap(),
+            big("1295")
+        );
+    }
+
+    #[test]
+    fn test_formatting() {
+        let value = big("255");
+        assert_eq!(
+            format!("{:x} {:#x} {:b} {:#010b}", value, value, value, value),
+            "ff 0xff 11111111 0b11111111"
+        );
+        assert_eq!(
+            format!("{:>6}|{:<6}|{:06}|{:+}", value, value, value, value),
+            "   255|255   |000255|+255"
+        );
+        assert_eq!(format!("{:x}", big("0")), "0");
+
+        let negative = SignedMediocreBigint::from_str("-42").unwrap();
+        assert_eq!(
+            format!("{}|{:>5}|{:05}", negative, negative, negative),
+            "-42|  -42|-0042"
+        );
+
+        // Inner limbs keep their zeros, outer ones do not
+        let digits = format!("7{}", "0123456789".repeat(10_000));
+        assert_eq!(big(&digits).to_string(), digits);
+        let limbs = MediocreBigint::from_limbs(&[5, 0, 42]);
+        assert_eq!(limbs.to_string(), format!("42{}5", "0".repeat(33)));
+        assert_eq!(MediocreBigint::from_limbs(&[0, 0]).to_string(), "0");
+    }
+
+    #[test]
+    fn test_primitive_operands_match_u128() {
+        let mut state = 0x3c6ef372fe94f82b;
+        for _ in 0..2000 {
+            let value = (next_random(&mut state) >> (next_random(&mut state) % 64)) as u128
+                * next_random(&mut state) as u128;
+            let small = next_random(&mut state) >> (next_random(&mut state) % 64);
+            let bigint = MediocreBigint::from(value);
+            if let Some(sum) = value.checked_add(small as u128) {
+                assert_eq!((bigint.clone() + small).to_u128(), Some(sum));
+            }
+            if let Some(product) = value.checked_mul(small as u128) {
+                assert_eq!((bigint.clone() * small).to_u128(), Some(product));
+            }
+            if small > 0 {
+                assert_eq!(
+                    (bigint.clone() / small).to_u128(),
+                    Some(value / small as u128)
+                );
+                assert_eq!(&bigint % small, (value % small as u128) as u64);
+            }
+        }
+        // Operands beyond u128 and limbs with pending carries
+        let huge = big("3").pow(300);
+        assert_eq!((huge.clone() * u64::MAX) / u64::MAX, huge);
+        let mut zeroed = huge.clone();
+        zeroed *= 0;
+        assert_eq!(zeroed, big("0"));
+        assert_eq!(huge.clone() % 3, 0);
+        assert_eq!((huge.clone() + 1) % 3, 1);
+        assert_eq!(big(&"9".repeat(34)) + 1, big("10").pow(34));
+        let carried = MediocreBigint::from_limbs(&[u64::MAX, u64::MAX]);
+        let value = u64::MAX as u128 * BIGINT_LIMIT as u128 + u64::MAX as u128;
+        assert_eq!(
+            (carried.clone() * u64::MAX).to_string(),
+            (MediocreBigint::from(value) * big(&u64::MAX.to_string())).to_string()
+        );
+        assert_eq!(
+            (carried.clone() + u64::MAX).to_u128(),
+            Some(value + u64::MAX as u128)
+        );
+        assert_eq!((carried.clone() / 7).to_u128(), Some(value / 7));
+        assert_eq!(carried % 1_000_000_007, (value % 1_000_000_007) as u64);
+        assert_eq!(MediocreBigint::from(12usize), big("12"));
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values: Vec<MediocreBigint> = (1..=30u64).map(MediocreBigint::from).collect();
+        assert_eq!(values.iter().sum::<MediocreBigint>(), big("465"));
+        assert_eq!(
+            values.iter().product::<MediocreBigint>(),
+            MediocreBigint::factorial(30)
+        );
+        assert_eq!(values.into_iter().rev().sum::<MediocreBigint>(), big("465"));
+        assert_eq!(
+            std::iter::empty::<MediocreBigint>().sum::<MediocreBigint>(),
+            big("0")
+        );
+        assert_eq!(
+            std::iter::empty::<MediocreBigint>().product::<MediocreBigint>(),
+            big("1")
+        );
+    }
+
+    #[test]
+    fn test_mul_full_limbs_and_carries() {
+        let nines = MediocreBigint::from_limbs(&[BIGINT_LIMIT - 1; 3]);
+        let square = nines.clone() * nines.clone();
+        assert_eq!(
+            square.to_string(),
+            format!("{}8{}1", "9".repeat(50), "0".repeat(50))
+        );
+
+        let one_limb = MediocreBigint::from(BIGINT_LIMIT - 1);
+        assert_eq!(
+            (nines.clone() * one_limb).to_string(),
+            "99999999999999998999999999999999999999999999999999900000000000000001"
+        );
+
+        // Pending carries on either side count with their value
+        let carried = MediocreBigint::from_limbs(&[BIGINT_LIMIT + 3]);
+        assert_eq!(
+            (carried.clone() * big("2")).to_string(),
+            "200000000000000006"
+        );
+        assert_eq!(
+            (big("2") * carried.clone()).to_string(),
+            "200000000000000006"
+        );
+        assert_eq!(
+            (nines * MediocreBigint::from_limbs(&[0, 0])).to_string(),
+            "0"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_rem_by_zero() {
+        let _ = big("5") % MediocreBigint { digits: vec![0, 0] };
+    }
+
+    fn signed(value: i128) -> SignedMediocreBigint {
+        SignedMediocreBigint::from_str(&value.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_signed_arithmetic_matches_i128() {
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        let mut values = vec![
+            0i128,
+            1,
+            -1,
+            99_999_999_999_999_999,
+            -100_000_000_000_000_000,
+        ];
+        for _ in 0..60 {
+            let random = next_random(&mut state);
+            values.push(random as i64 as i128 >> (random % 48));
+        }
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (signed(a), signed(b));
+                assert_eq!((x.clone() + y.clone()).to_string(), (a + b).to_string());
+                assert_eq!((x.clone() - y.clone()).to_string(), (a - b).to_string());
+                assert_eq!(x.cmp(&y), a.cmp(&b), "{} <=> {}", a, b);
+                assert_eq!(x == y, a == b);
+                assert_eq!((x.clone() * y.clone()).to_string(), (a * b).to_string());
+                if b != 0 {
+                    assert_eq!((x.clone() / y.clone()).to_string(), (a / b).to_string());
+                    assert_eq!((x % y).to_string(), (a % b).to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_parse_print_and_sign() {
+        let value = SignedMediocreBigint::from_str("-123456789012345678901234567890").unwrap();
+        assert!(value.is_negative());
+        assert_eq!(value.to_string(), "-123456789012345678901234567890");
+        assert_eq!(
+            (-value.clone()).to_string(),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(value.abs(), -value.clone());
+        assert_eq!(
+            value.magnitude().to_string(),
+            "123456789012345678901234567890"
+        );
+
+        let zero = SignedMediocreBigint::from_str("-0").unwrap();
+        assert!(!zero.is_negative());
+        assert_eq!(zero.to_string(), "0");
+        assert_eq!(-zero.clone(), SignedMediocreBigint::new());
+        assert_eq!((value.clone() - value.clone()).to_string(), "0");
+        assert!(!(value.clone() - value).is_negative());
+        assert_eq!(SignedMediocreBigint::from_str("+42").unwrap(), signed(42));
+        assert!(SignedMediocreBigint::from_str("--4").is_err());
+        for text in ["-", "+", "-+5", "+-5", ""] {
+            assert!(SignedMediocreBigint::from_str(text).is_err(), "{:?}", text);
+        }
+        assert!(MediocreBigint::from_str("11+1234567890123456").is_err());
+        assert!(MediocreBigint::from_str("").is_err());
+        assert_eq!(big("+00000000000000000012"), big("12"));
+    }
+
+    #[test]
+    fn test_conversions_and_lengths() {
+        for value in [
+            0u128,
+            1,
+            9,
+            10,
+            BIGINT_LIMIT as u128,
+            u64::MAX as u128,
+            u128::MAX,
+        ] {
+            let bigint = MediocreBigint::from(value);
+            assert_eq!(bigint.to_string(), value.to_string());
+            assert_eq!(bigint.to_u128(), Some(value));
+            assert_eq!(bigint.to_u64(), u64::try_from(value).ok());
+            assert_eq!(bigint.is_zero(), value == 0);
+            assert_eq!(bigint.digit_len(), value.to_string().len());
+            assert_eq!(bigint.bit_len(), 128 - value.leading_zeros() as u64);
+        }
+        assert_eq!(MediocreBigint::from(7u64), big("7"));
+
+        let carried = MediocreBigint::from_limbs(&[BIGINT_LIMIT, 0]);
+        assert_eq!(carried.limbs(), [BIGINT_LIMIT, 0]);
+        assert_eq!(carried.to_u64(), Some(BIGINT_LIMIT));
+        assert_eq!(carried.digit_len(), 18);
+        assert!(MediocreBigint::from_limbs(&[0, 0]).is_zero());
+
+        let huge = big("340282366920938463463374607431768211456");
+        assert_eq!(huge.to_u128(), None);
+        assert_eq!(huge.bit_len(), 129);
+        assert_eq!(huge.digit_len(), 39);
+    }
+
+    #[test]
+    fn test_numeric_equality_and_ordering() {
+        use std::collections::{BTreeMap, HashSet};
+        let zeros = [
+            MediocreBigint::new(),
+            MediocreBigint { digits: vec![0] },
+            MediocreBigint {
+                digits: vec![0, 0, 0],
+            },
+        ];
+        assert!(zeros.iter().all(|zero| *zero == zeros[0]));
+
+        let carried = MediocreBigint {
+            digits: vec![BIGINT_LIMIT + 3, BIGINT_LIMIT - 1, 0],
+        };
+        let normalized = MediocreBigint {
+            digits: vec![3, 0, 1],
+        };
+        assert_eq!(carried, normalized);
+        assert_eq!(carried.cmp(&normalized), std::cmp::Ordering::Equal);
+        assert!(big("99999999999999999") < carried);
+        assert!(carried < big("100000000000000000000000000000000001"));
+        assert!(MediocreBigint { digits: vec![5, 0] } > big("4"));
+
+        let set: HashSet<MediocreBigint> = zeros.iter().cloned().chain([carried.clone()]).collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&normalized));
+
+        let mut map = BTreeMap::new();
+        for text in ["300", "20", "1", "100000000000000000000", "20"] {
+            *map.entry(big(text)).or_insert(0) += 1;
+        }
+        let keys: Vec<String> = map.keys().map(|key| key.to_string()).collect();
+        assert_eq!(keys, ["1", "20", "300", "100000000000000000000"]);
+        assert_eq!(
+            map[&MediocreBigint {
+                digits: vec![20, 0]
+            }],
+            2
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_unsigned_underflow() {
+        let _ = MediocreBigint::from_str("4").unwrap() - MediocreBigint::from_str("5").unwrap();
+    }
+
+    #[test]
+    fn test_add_zero() {
+        let a = MediocreBigint::from_limbs(&[1, 0, 0, 1, 1]);
+        let zero = MediocreBigint::new();
+        assert_eq!(zero.to_string(), "0");
+        let c = a.clone() + zero.clone();
+        assert_eq!(c, a);
+
+        let d = zero + a.clone();
+        assert_eq!(d, a);
+    }
+
+    #[test]
+    fn test_mul_zero() {
+        let a = MediocreBigint::from_limbs(&[1, 0, 0, 1, 1]);
+        let zero = MediocreBigint::new();
+        assert_eq!(zero.to_string(), "0");
+        let c = a.clone() * zero.clone();
+        assert_eq!(c.to_string(), "0");
+
+        let d = zero.clone() * a.clone();
+        assert_eq!(d.to_string(), "0");
+    }
+
+    #[test]
+    fn test_mul_small() {
+        let a = MediocreBigint::from_limbs(&[2]);
+        let b = MediocreBigint::from_limbs(&[3]);
+        let c = a * b;
+        assert_eq!(c.limbs(), [6]);
+    }
+
+    #[test]
+    fn test_mul_carry() {
+        let a = MediocreBigint::from_limbs(&[7]);
+        let b = MediocreBigint::from_limbs(&[8]);
+        let c = a * b;
+        assert_eq!(c.to_string(), "56");
+    }
+
+    #[test]
+    fn test_mul_larger() {
+        let a = MediocreBigint::from_str("123").unwrap();
+        let b = MediocreBigint::from_str("456").unwrap();
+        let c = a * b;
+        assert_eq!(c, MediocreBigint::from_str("56088").unwrap());
+    }
+
+    #[test]
+    fn test_add_small() {
+        let a = MediocreBigint::from_limbs(&[2]);
+        let b = MediocreBigint::from_limbs(&[2]);
+        let c = a.clone() + b;
+        assert_eq!(c.limbs(), [4]);
+    }
+
+    #[test]
+    fn test_add_big() {
+        let a = MediocreBigint::from_limbs(&[2]);
+        let b = MediocreBigint::from_limbs(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+        let c = a.clone() + b;
+        assert_eq!(c.limbs(), [2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_add_carry() {
+        let a = MediocreBigint::from_str("1776").unwrap();
+        let b = MediocreBigint::from_str("76").unwrap();
+        let mut c = a + b;
+        c.normalize();
+        assert_eq!(
+            c,
+            MediocreBigint::from_str(&(1776 + 76).to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_mega_denorm() {
+        let a = MediocreBigint::from_limbs(&[MediocreDigitSize::MAX / 2]);
+        let mut b = a.clone();
+        b.normalize();
+        assert_eq!(a.limbs(), [MediocreDigitSize::MAX / 2]);
+        assert_eq!(
+            b.limbs(),
+            vec![
+                (MediocreDigitSize::MAX / 2) % BIGINT_LIMIT as MediocreDigitSize,
+                (MediocreDigitSize::MAX / 2) / BIGINT_LIMIT as MediocreDigitSize
+            ]
+        );
+
+        let mut c = a.clone() + a.clone();
+        let mut d = a.clone() + b.clone();
+        let mut e = b.clone() + b.clone();
+
+        c.normalize();
+        d.normalize();
+        e.normalize();
+        assert_eq!(c, d);
+        assert_eq!(d, e);
+    }
+
+    #[test]
+    fn test_simple_mega() {
+        let a = MediocreBigint::from_str("211111111111").unwrap();
+        let b = MediocreBigint::from_str("4").unwrap();
+        assert_eq!((a * b).to_string(), "844444444444");
+    }
+
+    #[test]
+    fn test_display_bookends() {
+        let a = MediocreBigint::from_str("7700000000000000000000042").unwrap();
+        assert_eq!(a.to_string(), "7700000000000000000000042");
+    }
+
+    #[test]
+    fn test_create() {
+        let int = MediocreBigint::new();
+        assert_eq!(int.limbs().len(), 0);
+    }
+
+    #[test]
+    fn test_empty_is_zero() {
+        let int = MediocreBigint::new();
+        assert_eq!(int.to_string(), "0");
+    }
+
+    #[test]
+    fn test_is_normalized() {
+        let a = MediocreBigint::from_limbs(&[]);
+        let b = MediocreBigint::from_limbs(&[2, 1, 1]);
+        let c = MediocreBigint::from_limbs(&[0, 0, 1, (BIGINT_LIMIT + 1) as MediocreDigitSize]);
+        assert!(a.is_normalized());
+        assert!(b.is_normalized());
+        assert!(!c.is_normalized());
+    }
+
+    #[test]
+    fn test_clone() {
+        let a = MediocreBigint::from_limbs(&[1, 1, 1, 2, 1]);
+        let b = a.clone();
+        assert_eq!(a, b);
+        let c = MediocreBigint::from_limbs(&[2, 1, 1, 2, 1]);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut a = MediocreBigint::from_limbs(&[1, BIGINT_LIMIT as MediocreDigitSize, 0, 1, 1]);
+        let b = a.clone();
+        assert_eq!(a, b);
+        a.normalize();
+        assert_ne!(a.limbs(), b.limbs());
+        assert_eq!(a, b);
+        assert!(a.is_normalized());
+        assert!(a.limbs().eq(&[1, 0, 1, 1, 1]));
+    }
+
+    #[test]
+    fn test_normalize_into_carry() {
+        let mut a = MediocreBigint::from_limbs(&[
+            1,
+            BIGINT_LIMIT as MediocreDigitSize,
+            BIGINT_LIMIT as MediocreDigitSize,
+            0,
+            1,
+        ]);
+        let b = a.clone();
+        assert_eq!(a, b);
+        a.normalize();
+        assert_ne!(a.limbs(), b.limbs());
+        assert_eq!(a, b);
+        assert!(a.is_normalized());
+        assert!(a.limbs().eq(&[1, 0, 1, 1, 1]));
+    }
+
+    #[test]
+    fn test_normalize_zero() {
+        let mut a = MediocreBigint::from_limbs(&[]);
+        a.normalize();
+        assert_eq!(a.limbs(), []);
+    }
+
+    #[test]
+    fn test_ten_is_ten() {
+        let mut a = MediocreBigint::from_str("10").unwrap();
+        a.normalize();
+        assert_eq!(a.to_string(), "10");
+    }
+
+    #[test]
+    fn test_normalize_create_digit() {
+        let mut a = MediocreBigint::from_limbs(&[BIGINT_LIMIT as MediocreDigitSize]);
+        a.normalize();
+        assert_eq!(a.limbs(), [0, 1]);
+    }
+
+    /// A slow but obviously correct reference for the differential tests: little-endian
+    /// decimal digits without zeros on top, so zero is empty
+    type Decimal = Vec<u8>;
+
+    fn decimal_trim(mut digits: Decimal) -> Decimal {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        digits
+    }
+
+    fn decimal_from_u64(mut value: u64) -> Decimal {
+        let mut digits = Vec::new();
+        while value > 0 {
+            digits.push((value % 10) as u8);
+            value /= 10;
+        }
+        digits
+    }
+
+    /// The value of limbs as `sum(limb_i * 10^(17 i))`, without going through `normalize`
+    fn decimal_from_limbs(limbs: &[MediocreDigitSize]) -> Decimal {
+        let mut value = Vec::new();
+        for (i, &limb) in limbs.iter().enumerate() {
+            let mut shifted = vec![0; i * LIMIT_POWER as usize];
+            shifted.extend(decimal_from_u64(limb));
+            value = decimal_add(&value, &decimal_trim(shifted));
+        }
+        value
+    }
+
+    /// The normalized limbs of a reference value, chunking 17 digits at a time
+    fn decimal_limbs(digits: &[u8]) -> Vec<MediocreDigitSize> {
+        digits
+            .chunks(LIMIT_POWER as usize)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0, |limb, &digit| limb * 10 + digit as u64)
+            })
+            .collect()
+    }
+
+    fn decimal_string(digits: &[u8]) -> String {
+        if digits.is_empty() {
+            return "0".to_string();
+        }
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| (b'0' + digit) as char)
+            .collect()
+    }
+
+    fn decimal_cmp(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn decimal_add(a: &[u8], b: &[u8]) -> Decimal {
+        let mut sum = Vec::new();
+        let mut carry = 0;
+        for i in 0..a.len().max(b.len()) {
+            let current = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+            sum.push(current % 10);
+            carry = current / 10;
+        }
+        sum.push(carry);
+        decimal_trim(sum)
+    }
+
+    /// `a - b` for `a >= b`
+    fn decimal_sub(a: &[u8], b: &[u8]) -> Decimal {
+        let mut difference = Vec::new();
+        let mut borrow = 0;
+        for (i, &digit) in a.iter().enumerate() {
+            let subtrahend = b.get(i).unwrap_or(&0) + borrow;
+            borrow = (digit < subtrahend) as u8;
+            difference.push(digit + 10 * borrow - subtrahend);
+        }
+        decimal_trim(difference)
+    }
+
+    fn decimal_mul(a: &[u8], b: &[u8]) -> Decimal {
+        let mut product = vec![0u32; a.len() + b.len() + 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] += x as u32 * y as u32;
+            }
+            // Carrying every row keeps the sums far from overflowing
+            for k in i..product.len() - 1 {
+                product[k + 1] += product[k] / 10;
+                product[k] %= 10;
+            }
+        }
+        decimal_trim(product.into_iter().map(|digit| digit as u8).collect())
+    }
+
+    /// Long division one decimal digit at a time, `b` must not be zero
+    fn decimal_div_rem(a: &[u8], b: &[u8]) -> (Decimal, Decimal) {
+        let mut quotient = vec![0; a.len()];
+        let mut remainder: Decimal = Vec::new();
+        for (i, &digit) in a.iter().enumerate().rev() {
+            remainder.insert(0, digit);
+            remainder = decimal_trim(remainder);
+            while decimal_cmp(&remainder, b) != std::cmp::Ordering::Less {
+                remainder = decimal_sub(&remainder, b);
+                quotient[i] += 1;
+            }
+        }
+        (decimal_trim(quotient), remainder)
+    }
+
+    /// Random limbs that are often denormalized: anything up to `u64::MAX` is a valid limb whose
+    /// carry `normalize` has yet to push up. Sometimes there are zero limbs on top, or no limbs.
+    fn random_operand(state: &mut u64, max_len: usize) -> Vec<MediocreDigitSize> {
+        let len = next_random(state) as usize % (max_len + 1);
+        let denormalized = next_random(state).is_multiple_of(3);
+        let mut limbs: Vec<_> = (0..len)
+            .map(
+                |_| match denormalized && next_random(state).is_multiple_of(2) {
+                    true => BIGINT_LIMIT + next_random(state) % (u64::MAX - BIGINT_LIMIT),
+                    false => random_limb(state),
+                },
+            )
+            .collect();
+        if next_random(state).is_multiple_of(8) {
+            limbs.extend([0, 0]);
+        }
+        limbs
+    }
+
+    /// Checks `actual` against the reference by its printed digits and by numeric equality with
+    /// limbs built straight from the reference
+    fn assert_matches(actual: &MediocreBigint, expected: &[u8], context: &str) {
+        assert_eq!(actual.to_string(), decimal_string(expected), "{}", context);
+        assert_eq!(
+            *actual,
+            MediocreBigint::from_limbs(&decimal_limbs(expected)),
+            "{}",
+            context
+        );
+    }
+
+    #[test]
+    fn test_operators_match_reference() {
+        let mut state = 0x6a09e667f3bcc908;
+        for round in 0..400 {
+            // A few operands reach past the Karatsuba threshold
+            let max_len = if round % 40 == 0 { 40 } else { 8 };
+            let (a, b) = (
+                random_operand(&mut state, max_len),
+                random_operand(&mut state, max_len),
+            );
+            let (x, y) = (
+                MediocreBigint::from_limbs(&a),
+                MediocreBigint::from_limbs(&b),
+            );
+            let (left, right) = (decimal_from_limbs(&a), decimal_from_limbs(&b));
+            let context = format!("{:?} and {:?}", a, b);
+
+            assert_matches(&x, &left, &context);
+            let mut normalized = x.clone();
+            normalized.normalize();
+            assert!(normalized.is_normalized(), "{}", context);
+            // Only zero limbs on top may remain above the reference's limbs
+            let expected_limbs = decimal_limbs(&left);
+            let (low, high) = normalized.limbs().split_at(expected_limbs.len());
+            assert_eq!(low, expected_limbs, "{}", context);
+            assert!(high.iter().all(|&limb| limb == 0), "{}", context);
+            assert_eq!(x.is_zero(), left.is_empty(), "{}", context);
+            assert_eq!(x.cmp(&y), decimal_cmp(&left, &right), "{}", context);
+            assert_eq!(x == y, left == right, "{}", context);
+
+            let sum = decimal_add(&left, &right);
+            assert_matches(&(x.clone() + y.clone()), &sum, &context);
+            let mut assigned = x.clone();
+            assigned += &y;
+            assert_matches(&assigned, &sum, &context);
+
+            let (larger, smaller, difference) = match decimal_cmp(&left, &right) {
+                std::cmp::Ordering::Less => (&y, &x, decimal_sub(&right, &left)),
+                _ => (&x, &y, decimal_sub(&left, &right)),
+            };
+            assert_matches(&(larger.clone() - smaller.clone()), &difference, &context);
+            let mut assigned = larger.clone();
+            assigned -= smaller;
+            assert_matches(&assigned, &difference, &context);
+
+            let product = decimal_mul(&left, &right);
+            assert_matches(&(x.clone() * y.clone()), &product, &context);
+            let mut assigned = x.clone();
+            assigned *= &y;
+            assert_matches(&assigned, &product, &context);
+
+            if !right.is_empty() {
+                let (quotient, remainder) = decimal_div_rem(&left, &right);
+                let (actual_quotient, actual_remainder) = x.div_rem(&y);
+                assert_matches(&actual_quotient, &quotient, &context);
+                assert_matches(&actual_remainder, &remainder, &context);
+                assert_matches(&(x.clone() / y.clone()), &quotient, &context);
+                assert_matches(&(x.clone() % y.clone()), &remainder, &context);
+                let mut assigned = x.clone();
+                assigned /= &y;
+                assert_matches(&assigned, &quotient, &context);
+                let mut assigned = x.clone();
+                assigned %= &y;
+                assert_matches(&assigned, &remainder, &context);
+            }
+
+            let small = match next_random(&mut state) % 3 {
+                0 => next_random(&mut state) % 10,
+                1 => next_random(&mut state) % BIGINT_LIMIT,
+                _ => next_random(&mut state),
+            };
+            let context = format!("{:?} and {}", a, small);
+            let small_decimal = decimal_from_u64(small);
+            assert_matches(
+                &(x.clone() + small),
+                &decimal_add(&left, &small_decimal),
+                &context,
+            );
+            assert_matches(
+                &(x.clone() * small),
+                &decimal_mul(&left, &small_decimal),
+                &context,
+            );
+            if small > 0 {
+                let (quotient, remainder) = decimal_div_rem(&left, &small_decimal);
+                assert_matches(&(x.clone() / small), &quotient, &context);
+                assert_eq!(decimal_from_u64(&x % small), remainder, "{}", context);
+            }
+        }
+    }
+
+    /// `value` as limbs, with some of each limb's value moved down into the one below it so
+    /// the limbs exceed `BIGINT_LIMIT`, and sometimes zero limbs on top
+    fn denormalized_limbs(value: u128, state: &mut u64) -> Vec<MediocreDigitSize> {
+        let base = BIGINT_LIMIT as u128;
+        let mut limbs = vec![
+            (value % base) as u64,
+            (value / base % base) as u64,
+            (value / base / base) as u64,
+        ];
+        for i in 0..2 {
+            let room = (u64::MAX - limbs[i]) / BIGINT_LIMIT;
+            let moved = next_random(state) % (room + 1);
+            let moved = moved.min(limbs[i + 1]);
+            limbs[i] += moved * BIGINT_LIMIT;
+            limbs[i + 1] -= moved;
+        }
+        if next_random(state).is_multiple_of(4) {
+            limbs.push(0);
+        }
+        limbs
+    }
+
+    #[test]
+    fn test_denormalized_operators_match_u128() {
+        let mut state = 0xbb67ae8584caa73b;
+        for _ in 0..3000 {
+            let (a, b) = (random_u128(&mut state), random_u128(&mut state));
+            let (a_limbs, b_limbs) = (
+                denormalized_limbs(a, &mut state),
+                denormalized_limbs(b, &mut state),
+            );
+            let (x, y) = (
+                MediocreBigint::from_limbs(&a_limbs),
+                MediocreBigint::from_limbs(&b_limbs),
+            );
+            let context = format!("{:?} and {:?}", a_limbs, b_limbs);
+
+            assert_eq!(x.to_u128(), Some(a), "{}", context);
+            assert_eq!(x.to_string(), a.to_string(), "{}", context);
+            assert_eq!(x.digit_len(), a.to_string().len(), "{}", context);
+            assert_eq!(x.bit_len(), 128 - a.leading_zeros() as u64, "{}", context);
+            assert_eq!(x.cmp(&y), a.cmp(&b), "{}", context);
+            let mut normalized = x.clone();
+            normalized.normalize();
+            assert!(normalized.is_normalized(), "{}", context);
+            assert_eq!(normalized, MediocreBigint::from(a), "{}", context);
+
+            if let Some(sum) = a.checked_add(b) {
+                assert_eq!((x.clone() + y.clone()).to_u128(), Some(sum), "{}", context);
+            }
+            if a >= b {
+                assert_eq!(
+                    (x.clone() - y.clone()).to_u128(),
+                    Some(a - b),
+                    "{}",
+                    context
+                );
+            }
+            if let Some(product) = a.checked_mul(b) {
+                assert_eq!(
+                    (x.clone() * y.clone()).to_u128(),
+                    Some(product),
+                    "{}",
+                    context
+                );
+            }
+            if b > 0 {
+                assert_eq!(
+                    (x.clone() / y.clone()).to_u128(),
+                    Some(a / b),
+                    "{}",
+                    context
+                );
+                assert_eq!(
+                    (x.clone() % y.clone()).to_u128(),
+                    Some(a % b),
+                    "{}",
+                    context
+                );
+            }
+
+            let small = b as u64;
+            if let Some(sum) = a.checked_add(small as u128) {
+                assert_eq!((x.clone() + small).to_u128(), Some(sum), "{}", context);
+            }
+            if let Some(product) = a.checked_mul(small as u128) {
+                assert_eq!((x.clone() * small).to_u128(), Some(product), "{}", context);
+            }
+            if small > 0 {
+                assert_eq!((x.clone() / small).to_u128(), Some(a / small as u128));
+                assert_eq!(&x % small, (a % small as u128) as u64, "{}", context);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "unstable", test))]
+mod benches {
+    use super::*;
+    use crate::test_random::next_random;
+    use test::Bencher;
+
+    /// Roughly the size of the largest `setnja` answers, 5^10000 has about 7000 digits
+    const SETNJA_LIMBS: usize = 420;
+
+    fn operand(length: usize, seed: u64) -> Vec<MediocreDigitSize> {
+        let mut state = seed;
+        (0..length)
+            .map(|_| next_random(&mut state) % BIGINT_LIMIT)
+            .collect()
+    }
+
+    /// The previous multiplication, which added up one freshly allocated partial product per
+    /// limb of `rhs`
+    fn partial_sums_product(lhs: &MediocreBigint, rhs: &MediocreBigint) -> MediocreBigint {
+        let mut result = MediocreBigint::new();
+        for (i, &r_digit) in rhs.digits.iter().enumerate() {
+            if r_digit == 0 {
+                continue;
+            }
+            let mut partial_result = Vec::with_capacity(i + lhs.digits.len() + 2);
+            partial_result.extend(std::iter::repeat_n(0, i));
+            let mut carry: u128 = 0;
+            for &l_digit in lhs.digits.iter() {
+                let product = l_digit as u128 * r_digit as u128 + carry;
+                partial_result.push((product % BIGINT_LIMIT as u128) as MediocreDigitSize);
+                carry = product / BIGINT_LIMIT as u128;
+            }
+            while carry > 0 {
+                partial_result.push((carry % BIGINT_LIMIT as u128) as MediocreDigitSize);
+                carry /= BIGINT_LIMIT as u128;
+            }
+            result += MediocreBigint {
+                digits: partial_result,
+            };
+        }
+        result
+    }
+
+    fn bench_product(
+        b: &mut Bencher,
+        length: usize,
+        product: fn(&[MediocreDigitSize], &[MediocreDigitSize]) -> Vec<MediocreDigitSize>,
+    ) {
+        let (lhs, rhs) = (operand(length, 1), operand(length, 2));
+        b.iter(|| product(test::black_box(&lhs), test::black_box(&rhs)))
+    }
+
+    #[bench]
+    fn bench_mul_setnja(b: &mut Bencher) {
+        let lhs = MediocreBigint::from_limbs(&operand(SETNJA_LIMBS, 1));
+        let rhs = MediocreBigint::from_limbs(&operand(SETNJA_LIMBS, 2));
+        b.iter(|| test::black_box(&lhs).clone() * test::black_box(&rhs).clone())
+    }
+
+    #[bench]
+    fn bench_mul_setnja_partial_sums(b: &mut Bencher) {
+        let lhs = MediocreBigint::from_limbs(&operand(SETNJA_LIMBS, 1));
+        let rhs = MediocreBigint::from_limbs(&operand(SETNJA_LIMBS, 2));
+        b.iter(|| partial_sums_product(test::black_box(&lhs), test::black_box(&rhs)))
+    }
+
+    #[bench]
+    fn bench_mul_setnja_schoolbook(b: &mut Bencher) {
+        bench_product(b, SETNJA_LIMBS, mul_schoolbook)
+    }
+
+    // Around the threshold, to tune `KARATSUBA_THRESHOLD`
+    #[bench]
+    fn bench_mul_32_limbs_schoolbook(b: &mut Bencher) {
+        bench_product(b, 32, mul_schoolbook)
+    }
+
+    #[bench]
+    fn bench_mul_32_limbs_karatsuba(b: &mut Bencher) {
+        bench_product(b, 32, mul_karatsuba)
+    }
+
+    #[bench]
+    fn bench_mul_64_limbs_schoolbook(b: &mut Bencher) {
+        bench_product(b, 64, mul_schoolbook)
+    }
+
+    #[bench]
+    fn bench_mul_64_limbs_karatsuba(b: &mut Bencher) {
+        bench_product(b, 64, mul_karatsuba)
+    }
+
+    #[bench]
+    fn bench_display_100k_digits(b: &mut Bencher) {
+        let value = MediocreBigint::from_limbs(&operand(100_000 / LIMIT_POWER as usize, 3));
+        b.iter(|| test::black_box(&value).to_string())
+    }
+
+    #[bench]
+    fn bench_mul_setnja_ntt(b: &mut Bencher) {
+        bench_product(b, SETNJA_LIMBS, mul_ntt)
+    }
+
+    // Around `NTT_THRESHOLD`
+    #[bench]
+    fn bench_mul_2048_limbs_karatsuba(b: &mut Bencher) {
+        bench_product(b, 2048, mul_karatsuba)
+    }
+
+    #[bench]
+    fn bench_mul_2048_limbs_ntt(b: &mut Bencher) {
+        bench_product(b, 2048, mul_ntt)
     }
 }
@@ -0,0 +1,217 @@
+/// 998244353 = 119 * 2^23 + 1, the usual modulus for counting problems that need convolutions
+pub const MOD: u64 = 998_244_353;
+
+/// NTT-friendly primes that all have 3 as a primitive root. Together they reconstruct
+/// coefficients below their product, about 7.8 * 10^25, through the Chinese remainder theorem.
+const PRIMES: [u64; 3] = [998_244_353, 167_772_161, 469_762_049];
+const PRIMITIVE_ROOT: u64 = 3;
+
+/// Below this many terms in the shorter input, the quadratic convolution is faster
+const NAIVE_THRESHOLD: usize = 64;
+
+/// The product of the polynomials `a` and `b` with coefficients mod `MOD`, lowest term first
+pub fn convolution(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let (a, b) = (reduced(a, MOD), reduced(b, MOD));
+    if a.len().min(b.len()) < NAIVE_THRESHOLD {
+        return naive_convolution(&a, &b)
+            .into_iter()
+            .map(|term| (term % MOD as u128) as u64)
+            .collect();
+    }
+    convolution_prime(&a, &b, MOD)
+}
+
+/// The product of the polynomials `a` and `b` with coefficients mod any `modulus` up to 2^31,
+/// e.g. 10^9 + 7, which is not NTT-friendly itself
+pub fn convolution_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    assert!(
+        modulus > 0 && modulus <= 1 << 31,
+        "convolution modulus {} is not in 1..=2^31",
+        modulus
+    );
+    convolution_exact(&reduced(a, modulus), &reduced(b, modulus))
+        .into_iter()
+        .map(|term| (term % modulus as u128) as u64)
+        .collect()
+}
+
+/// The product of the polynomials `a` and `b` without any modulus. Only exact while every
+/// coefficient of the result stays below about 7.8 * 10^25, which holds for inputs below 2^31.
+pub fn convolution_exact(a: &[u64], b: &[u64]) -> Vec<u128> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < NAIVE_THRESHOLD {
+        return naive_convolution(a, b);
+    }
+    let residues =
+        PRIMES.map(|prime| convolution_prime(&reduced(a, prime), &reduced(b, prime), prime));
+
+    let [p0, p1, p2] = PRIMES;
+    let p0_inverse = pow_mod(p0 % p1, p1 - 2, p1);
+    let p01_inverse = pow_mod((p0 * p1) % p2, p2 - 2, p2);
+    (0..residues[0].len())
+        .map(|i| {
+            // Garner's algorithm: x = r0 + p0 * t1 + p0 * p1 * t2
+            let (r0, r1, r2) = (residues[0][i], residues[1][i], residues[2][i]);
+            let t1 = (r1 + p1 - r0 % p1) % p1 * p0_inverse % p1;
+            let low = r0 as u128 + p0 as u128 * t1 as u128;
+            let t2 = (r2 + p2 - (low % p2 as u128) as u64) % p2 * p01_inverse % p2;
+            low + (p0 * p1) as u128 * t2 as u128
+        })
+        .collect()
+}
+
+fn reduced(values: &[u64], modulus: u64) -> Vec<u64> {
+    values.iter().map(|value| value % modulus).collect()
+}
+
+fn naive_convolution(a: &[u64], b: &[u64]) -> Vec<u128> {
+    let mut product = vec![0u128; a.len() + b.len() - 1];
+    for (i, &left) in a.iter().enumerate() {
+        for (j, &right) in b.iter().enumerate() {
+            product[i + j] += left as u128 * right as u128;
+        }
+    }
+    product
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Convolution of inputs already reduced mod `prime`, one of `PRIMES`
+fn convolution_prime(a: &[u64], b: &[u64], prime: u64) -> Vec<u64> {
+    let result_len = a.len() + b.len() - 1;
+    let size = result_len.next_power_of_two();
+    assert!(
+        size <= 1 << (prime - 1).trailing_zeros(),
+        "convolution of {} terms is too long for the NTT",
+        result_len
+    );
+    let mut a_values = a.to_vec();
+    let mut b_values = b.to_vec();
+    a_values.resize(size, 0);
+    b_values.resize(size, 0);
+    transform(&mut a_values, prime, false);
+    transform(&mut b_values, prime, false);
+    for (left, right) in a_values.iter_mut().zip(&b_values) {
+        *left = *left * right % prime;
+    }
+    transform(&mut a_values, prime, true);
+    a_values.truncate(result_len);
+    a_values
+}
+
+/// In-place iterative number-theoretic transform of a power-of-two length, or its inverse
+fn transform(values: &mut [u64], prime: u64, invert: bool) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut twiddles = Vec::with_capacity(n / 2);
+    let mut length = 2;
+    while length <= n {
+        let mut root = pow_mod(PRIMITIVE_ROOT, (prime - 1) / length as u64, prime);
+        if invert {
+            root = pow_mod(root, prime - 2, prime);
+        }
+        let half = length / 2;
+        twiddles.clear();
+        let mut twiddle = 1;
+        for _ in 0..half {
+            twiddles.push(twiddle);
+            twiddle = twiddle * root % prime;
+        }
+        for block in values.chunks_exact_mut(length) {
+            let (low, high) = block.split_at_mut(half);
+            for ((u, v), &twiddle) in low.iter_mut().zip(high.iter_mut()).zip(&twiddles) {
+                let (x, y) = (*u, *v * twiddle % prime);
+                *u = if x + y >= prime { x + y - prime } else { x + y };
+                *v = if x >= y { x - y } else { x + prime - y };
+            }
+        }
+        length <<= 1;
+    }
+
+    if invert {
+        let n_inverse = pow_mod(n as u64, prime - 2, prime);
+        for value in values.iter_mut() {
+            *value = *value * n_inverse % prime;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_random::next_random;
+
+    fn random_values(length: usize, bound: u64, state: &mut u64) -> Vec<u64> {
+        (0..length).map(|_| next_random(state) % bound).collect()
+    }
+
+    #[test]
+    fn test_convolution_matches_naive() {
+        let mut state = 0x853c49e6748fea9b;
+        for (a_len, b_len) in [(1, 1), (3, 200), (64, 64), (300, 517), (1000, 999)] {
+            let a = random_values(a_len, u64::MAX, &mut state);
+            let b = random_values(b_len, u64::MAX, &mut state);
+            let expected: Vec<u64> = naive_convolution(&reduced(&a, MOD), &reduced(&b, MOD))
+                .into_iter()
+                .map(|term| (term % MOD as u128) as u64)
+                .collect();
+            assert_eq!(convolution(&a, &b), expected, "{} x {}", a_len, b_len);
+        }
+        assert_eq!(convolution(&[], &[1, 2]), Vec::<u64>::new());
+        assert_eq!(convolution(&[1, 1], &[1, 1]), [1, 2, 1]);
+    }
+
+    #[test]
+    fn test_convolution_mod_and_exact() {
+        let mut state = 0xda3e39cb94b95bdb;
+        for modulus in [1_000_000_007, 2, 1 << 31] {
+            let a = random_values(700, u64::MAX, &mut state);
+            let b = random_values(300, u64::MAX, &mut state);
+            let expected: Vec<u64> =
+                naive_convolution(&reduced(&a, modulus), &reduced(&b, modulus))
+                    .into_iter()
+                    .map(|term| (term % modulus as u128) as u64)
+                    .collect();
+            assert_eq!(convolution_mod(&a, &b, modulus), expected);
+        }
+
+        // The largest inputs the exact convolution promises to handle
+        let a = vec![(1 << 31) - 1; 2000];
+        let b = random_values(1500, 1 << 31, &mut state);
+        assert_eq!(convolution_exact(&a, &b), naive_convolution(&a, &b));
+    }
+
+    #[test]
+    #[should_panic(expected = "convolution modulus")]
+    fn test_convolution_mod_rejects_large_modulus() {
+        convolution_mod(&[1], &[1], (1 << 31) + 1);
+    }
+}
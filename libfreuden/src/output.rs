@@ -0,0 +1,327 @@
+use std::io::Write;
+
+/// Buffered output flushes once this much has been collected
+const FLUSH_THRESHOLD: usize = 1 << 16;
+
+/// Pairs of decimal digits, so integers are written two digits per division
+const DIGIT_PAIRS: &[u8; 200] = b"0001020304050607080910111213141516171819\
+2021222324252627282930313233343536373839\
+4041424344454647484950515253545556575859\
+6061626364656667686970717273747576777879\
+8081828384858687888990919293949596979899";
+
+/// Collects output in a buffer and writes it out in large chunks, converting integers without
+/// going through `format!`. Whatever is left is flushed when the writer is dropped.
+///
+/// ```ignore
+/// let mut out = FastWriter::stdout();
+/// out.write(answer).newline();
+/// out.write_separated(&values, " ").newline();
+/// out.write_float(ratio, 6).newline();
+/// ```
+pub struct FastWriter<W: Write> {
+    writer: W,
+    buffer: Vec<u8>,
+}
+
+impl FastWriter<std::io::StdoutLock<'static>> {
+    pub fn stdout() -> Self {
+        FastWriter::new(std::io::stdout().lock())
+    }
+}
+
+impl<W: Write> FastWriter<W> {
+    pub fn new(writer: W) -> Self {
+        FastWriter {
+            writer,
+            buffer: Vec::with_capacity(FLUSH_THRESHOLD + 64),
+        }
+    }
+
+    pub fn write<T: Print>(&mut self, value: T) -> &mut Self {
+        value.print(&mut self.buffer);
+        self.flush_if_full()
+    }
+
+    pub fn write_line<T: Print>(&mut self, value: T) -> &mut Self {
+        self.write(value).newline()
+    }
+
+    /// Writes `value` rounded to `precision` digits after the decimal point
+    pub fn write_float(&mut self, value: f64, precision: usize) -> &mut Self {
+        write!(&mut self.buffer, "{:.*}", precision, value).unwrap();
+        self.flush_if_full()
+    }
+
+    /// Writes the values with `separator` between them, e.g. `write_separated(&row, " ")`
+    pub fn write_separated<T: Print, I: IntoIterator<Item = T>>(
+        &mut self,
+        values: I,
+        separator: &str,
+    ) -> &mut Self {
+        for (index, value) in values.into_iter().enumerate() {
+            if index > 0 {
+                self.buffer.extend_from_slice(separator.as_bytes());
+            }
+            self.write(value);
+        }
+        self
+    }
+
+    pub fn space(&mut self) -> &mut Self {
+        self.buffer.push(b' ');
+        self
+    }
+
+    pub fn newline(&mut self) -> &mut Self {
+        self.buffer.push(b'\n');
+        self.flush_if_full()
+    }
+
+    fn flush_if_full(&mut self) -> &mut Self {
+        if self.buffer.len() >= FLUSH_THRESHOLD {
+            self.writer
+                .write_all(&self.buffer)
+                .expect("Failed to write output");
+            self.buffer.clear();
+        }
+        self
+    }
+}
+
+/// Lets `write!` and `writeln!` go through the same buffer
+impl<W: Write> Write for FastWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        self.flush_if_full();
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for FastWriter<W> {
+    fn drop(&mut self) {
+        let _ = Write::flush(self);
+    }
+}
+
+/// A value `FastWriter` knows how to append to its buffer
+pub trait Print {
+    fn print(&self, buffer: &mut Vec<u8>);
+}
+
+impl<T: Print + ?Sized> Print for &T {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        (**self).print(buffer)
+    }
+}
+
+impl Print for str {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Print for String {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Print for [u8] {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self);
+    }
+}
+
+impl Print for char {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        let mut encoded = [0u8; 4];
+        buffer.extend_from_slice(self.encode_utf8(&mut encoded).as_bytes());
+    }
+}
+
+/// Floats keep Rust's shortest round-trip formatting; use `write_float` for fixed precision
+impl Print for f64 {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        write!(buffer, "{}", self).unwrap();
+    }
+}
+
+impl Print for f32 {
+    fn print(&self, buffer: &mut Vec<u8>) {
+        write!(buffer, "{}", self).unwrap();
+    }
+}
+
+/// Appends the decimal digits of `value`, filling a scratch buffer from the back
+fn print_u128(mut value: u128, buffer: &mut Vec<u8>) {
+    let mut digits = [0u8; 39];
+    let mut start = digits.len();
+    while value >= 100 {
+        let pair = (value % 100) as usize * 2;
+        value /= 100;
+        start -= 2;
+        digits[start..start + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    }
+    if value >= 10 {
+        let pair = value as usize * 2;
+        start -= 2;
+        digits[start..start + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    } else {
+        start -= 1;
+        digits[start] = b'0' + value as u8;
+    }
+    buffer.extend_from_slice(&digits[start..]);
+}
+
+/// Same as `print_u128`, but with 64-bit divisions, which are much cheaper than 128-bit ones
+fn print_u64(mut value: u64, buffer: &mut Vec<u8>) {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    while value >= 100 {
+        let pair = (value % 100) as usize * 2;
+        value /= 100;
+        start -= 2;
+        digits[start..start + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    }
+    if value >= 10 {
+        let pair = value as usize * 2;
+        start -= 2;
+        digits[start..start + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    } else {
+        start -= 1;
+        digits[start] = b'0' + value as u8;
+    }
+    buffer.extend_from_slice(&digits[start..]);
+}
+
+macro_rules! print_unsigned {
+    ($print:ident: $($int:ty),*) => {$(
+        impl Print for $int {
+            fn print(&self, buffer: &mut Vec<u8>) {
+                $print(*self as _, buffer);
+            }
+        }
+    )*};
+}
+print_unsigned!(print_u64: u8, u16, u32, u64, usize);
+print_unsigned!(print_u128: u128);
+
+macro_rules! print_signed {
+    ($print:ident: $($int:ty),*) => {$(
+        impl Print for $int {
+            fn print(&self, buffer: &mut Vec<u8>) {
+                if self.is_negative() {
+                    buffer.push(b'-');
+                }
+                $print(self.unsigned_abs() as _, buffer);
+            }
+        }
+    )*};
+}
+print_signed!(print_u64: i8, i16, i32, i64, isize);
+print_signed!(print_u128: i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(fill: impl FnOnce(&mut FastWriter<&mut Vec<u8>>)) -> String {
+        let mut output = Vec::new();
+        fill(&mut FastWriter::new(&mut output));
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_write_integers() {
+        let output = written(|out| {
+            out.write(0u8)
+                .space()
+                .write(7u32)
+                .space()
+                .write(10usize)
+                .newline();
+            out.write(u64::MAX).newline().write(u128::MAX).newline();
+            out.write(-1i32)
+                .space()
+                .write(i64::MIN)
+                .space()
+                .write(i128::MIN)
+                .newline();
+            out.write(i8::MIN)
+                .space()
+                .write(99u16)
+                .space()
+                .write(100i16)
+                .newline();
+        });
+        assert_eq!(
+            output,
+            format!(
+                "0 7 10\n{}\n{}\n-1 {} {}\n-128 99 100\n",
+                u64::MAX,
+                u128::MAX,
+                i64::MIN,
+                i128::MIN
+            )
+        );
+    }
+
+    #[test]
+    fn test_write_integers_matches_display() {
+        let output = written(|out| {
+            for value in (-100_000i64..100_000).step_by(7).chain([999, 1000, 1001]) {
+                out.write(value).newline();
+            }
+        });
+        let expected: String = (-100_000i64..100_000)
+            .step_by(7)
+            .chain([999, 1000, 1001])
+            .map(|value| format!("{}\n", value))
+            .collect();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_write_floats_strings_and_separators() {
+        let output = written(|out| {
+            out.write_float(2.0 / 3.0, 6)
+                .space()
+                .write_float(-0.5, 0)
+                .space();
+            out.write_float(1e-7, 3).newline();
+            out.write(1.5f64)
+                .space()
+                .write("text")
+                .space()
+                .write('é')
+                .newline();
+            let row: Vec<u64> = (1..=3).collect();
+            out.write_separated(&row, " ").newline();
+            out.write_separated(["a", "b"], ", ").newline();
+            out.write_line(String::from("done"));
+            writeln!(out, "{:>3}", 4).unwrap();
+        });
+        assert_eq!(
+            output,
+            "0.666667 -0 0.000\n1.5 text é\n1 2 3\na, b\ndone\n  4\n"
+        );
+    }
+
+    #[test]
+    fn test_large_output_is_flushed_in_order() {
+        let output = written(|out| {
+            for value in 0..100_000u32 {
+                out.write_line(value);
+            }
+        });
+        assert_eq!(output.lines().count(), 100_000);
+        assert_eq!(output.lines().nth(77_777), Some("77777"));
+    }
+}
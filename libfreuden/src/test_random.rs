@@ -0,0 +1,14 @@
+/// Deterministic xorshift pseudo-random numbers for the tests and benches, so failures
+/// reproduce without a dependency. `state` must not start at zero.
+pub fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// A full-width value shifted down by a random amount, so small and large values both show up
+pub fn random_u128(state: &mut u64) -> u128 {
+    let value = (next_random(state) as u128) << 64 | next_random(state) as u128;
+    value >> (next_random(state) % 128)
+}
@@ -0,0 +1,48 @@
+/// Bundles a solution crate into one self-contained file for submission.
+///
+/// Usage: bundle <crate dir | file.rs> [--bin <name>] [--output <path> | --output -] [--check]
+///               [--no-prune]
+use kattis_tools::cli::{Args, finish};
+use kattis_tools::workspace::{self, SolutionTarget};
+use std::path::{Path, PathBuf};
+
+fn run() -> Result<(), String> {
+    let args = Args::from_env(&["bin", "output"])?;
+    let [path] = args.positional() else {
+        return Err(
+            "usage: bundle <crate dir | file.rs> [--bin <name>] [--output <path>] [--check] [--no-prune]"
+                .to_string(),
+        );
+    };
+
+    let target = SolutionTarget::resolve(Path::new(path), args.value("bin"))?;
+    let root = workspace::find_root(&target.crate_dir)?;
+    let bundled = kattis_tools::bundle::bundle(
+        &target,
+        &workspace::libfreuden_src(&root),
+        !args.flag("no-prune"),
+    )?;
+
+    if args.value("output") == Some("-") {
+        print!("{}", bundled);
+        return Ok(());
+    }
+
+    let output = args
+        .value("output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| target.crate_dir.join("submission.rs"));
+    std::fs::write(&output, &bundled)
+        .map_err(|error| format!("failed to write {}: {}", output.display(), error))?;
+    eprintln!("wrote {} ({} bytes)", output.display(), bundled.len());
+
+    if args.flag("check") {
+        kattis_tools::bundle::check(&output, target.manifest()?.edition())?;
+        eprintln!("{} compiles with rustc", output.display());
+    }
+    Ok(())
+}
+
+fn main() {
+    finish(run());
+}
@@ -0,0 +1,56 @@
+/// Writes one solution from `combined_trivial` / `combined_medium` as a standalone submission.
+///
+/// Usage: extract <problem id | fn name> [--crate <combined crate dir>]
+///                [--output <path> | --output -] [--check] [--no-prune]
+use kattis_tools::cli::{Args, finish};
+use kattis_tools::extract::{self, COMBINED_CRATES};
+use kattis_tools::manifest::Manifest;
+use kattis_tools::workspace;
+use std::path::PathBuf;
+
+fn run() -> Result<(), String> {
+    let args = Args::from_env(&["crate", "output"])?;
+    let [problem] = args.positional() else {
+        return Err(
+            "usage: extract <problem id | fn name> [--crate <dir>] [--output <path>] [--check] [--no-prune]"
+                .to_string(),
+        );
+    };
+
+    let root = workspace::current_root()?;
+    let crate_dirs: Vec<PathBuf> = match args.value("crate") {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => COMBINED_CRATES.iter().map(|path| root.join(path)).collect(),
+    };
+    let (crate_dir, function) = extract::find_problem(&crate_dirs, problem)?;
+    let extracted = extract::extract(
+        &crate_dir,
+        &function,
+        &workspace::libfreuden_src(&root),
+        !args.flag("no-prune"),
+    )?;
+
+    if args.value("output") == Some("-") {
+        print!("{}", extracted);
+        return Ok(());
+    }
+
+    let output = args
+        .value("output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate_dir.join("submission.rs"));
+    std::fs::write(&output, &extracted)
+        .map_err(|error| format!("failed to write {}: {}", output.display(), error))?;
+    eprintln!("wrote {} ({} bytes)", output.display(), extracted.len());
+
+    if args.flag("check") {
+        let manifest = Manifest::read(&crate_dir.join("Cargo.toml"))?;
+        kattis_tools::bundle::check(&output, manifest.edition())?;
+        eprintln!("{} compiles with rustc", output.display());
+    }
+    Ok(())
+}
+
+fn main() {
+    finish(run());
+}
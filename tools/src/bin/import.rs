@@ -0,0 +1,55 @@
+/// Creates `progress/<problem id>` from a Kattis problem package downloaded to disk.
+///
+/// Usage: import <package dir | package.zip> [--id <problem id>] [--difficulty <points>]
+///        [--time-limit <seconds>]
+/// The options fill in (or override) what the package's `problem.yaml` does not say.
+use kattis_tools::cli::{Args, finish};
+use kattis_tools::package::{self, ProblemPackage};
+use kattis_tools::workspace;
+use std::path::Path;
+
+fn run() -> Result<(), String> {
+    let args = Args::from_env(&["id", "difficulty", "time-limit"])?;
+    let [path] = args.positional() else {
+        return Err(
+            "usage: import <package dir | package.zip> [--id <problem id>] [--difficulty <points>] [--time-limit <seconds>]"
+                .to_string(),
+        );
+    };
+
+    let scratch = std::env::temp_dir().join(format!("kattis_import_{}", std::process::id()));
+    let opened = ProblemPackage::open(Path::new(path), &scratch);
+    let result = opened.and_then(|mut problem| {
+        if let Some(id) = args.value("id") {
+            problem.id = id.to_string();
+        }
+        if let Some(difficulty) = args.value("difficulty") {
+            problem.difficulty = Some(parse_number("--difficulty", difficulty)?);
+        }
+        if let Some(seconds) = args.value("time-limit") {
+            problem.time_limit = Some(parse_number("--time-limit", seconds)?);
+        }
+        let crate_dir = package::create_crate(&workspace::current_root()?, &problem)?;
+        println!(
+            "created {} ({} samples)",
+            crate_dir.display(),
+            problem.samples.len()
+        );
+        if problem.difficulty.is_none() {
+            eprintln!("note: no difficulty recorded, pass --difficulty to set it");
+        }
+        Ok(())
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+fn parse_number(option: &str, value: &str) -> Result<f64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} takes a number, not {}", option, value))
+}
+
+fn main() {
+    finish(run());
+}
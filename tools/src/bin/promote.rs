@@ -0,0 +1,61 @@
+/// Moves a solved crate out of `progress/` once its samples pass.
+///
+/// Usage: promote <crate dir> [--difficulty <points>] [--merge] [--timeout <seconds>]
+/// The crate lands in `completed/<trivial|medium|hard>/` by its recorded difficulty. With
+/// `--merge`, a single-function solution becomes `fn <problem id>()` in the tier's combined crate.
+use kattis_tools::cli::{Args, finish};
+use kattis_tools::promote;
+use kattis_tools::samples;
+use kattis_tools::workspace;
+use std::path::Path;
+use std::time::Duration;
+
+fn run() -> Result<(), String> {
+    let args = Args::from_env(&["difficulty", "timeout"])?;
+    let [crate_dir] = args.positional() else {
+        return Err(
+            "usage: promote <crate dir> [--difficulty <points>] [--merge] [--timeout <seconds>]"
+                .to_string(),
+        );
+    };
+    let crate_dir = Path::new(crate_dir);
+    let root = workspace::find_root(crate_dir)?;
+    let timeout = match args.value("timeout") {
+        Some(seconds) => Duration::from_secs_f64(
+            seconds
+                .parse()
+                .map_err(|_| format!("--timeout takes seconds, not {}", seconds))?,
+        ),
+        None => Duration::from_secs(10),
+    };
+
+    if let Some(difficulty) = args.value("difficulty") {
+        let difficulty = difficulty
+            .parse()
+            .map_err(|_| format!("--difficulty takes a number, not {}", difficulty))?;
+        promote::record_difficulty(crate_dir, difficulty)?;
+    }
+    let difficulty = promote::recorded_difficulty(crate_dir)?.ok_or_else(|| {
+        format!(
+            "{} has no recorded difficulty, pass --difficulty",
+            crate_dir.display()
+        )
+    })?;
+    let tier = promote::tier(difficulty);
+
+    let (passed, total) = samples::verify(crate_dir, timeout)?;
+    println!("{}/{} samples passed", passed, total);
+
+    if args.flag("merge") {
+        let combined = promote::merge(&root, crate_dir, tier)?;
+        println!("merged into {}", combined.display());
+    } else {
+        let destination = promote::move_crate(&root, crate_dir, tier)?;
+        println!("moved to {}", destination.display());
+    }
+    Ok(())
+}
+
+fn main() {
+    finish(run());
+}
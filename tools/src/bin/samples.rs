@@ -0,0 +1,84 @@
+/// Runs solution binaries against their `samples/*.in` / `*.ans` pairs and reports the verdicts.
+///
+/// Usage: samples [crate dir...] [--release] [--timeout <seconds>] [--validator-flags <flags>]
+/// Without a crate, every workspace crate that has a `samples` directory is run.
+use kattis_tools::cli::{Args, finish};
+use kattis_tools::samples::{self, Comparison, Verdict};
+use kattis_tools::workspace;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn run() -> Result<(), String> {
+    let args = Args::from_env(&["timeout", "validator-flags"])?;
+    let release = args.flag("release");
+    let timeout = match args.value("timeout") {
+        Some(seconds) => Duration::from_secs_f64(
+            seconds
+                .parse()
+                .map_err(|_| format!("--timeout takes seconds, not {}", seconds))?,
+        ),
+        None => Duration::from_secs(10),
+    };
+    let override_comparison = args
+        .value("validator-flags")
+        .map(Comparison::from_validator_flags)
+        .transpose()?;
+
+    let crates: Vec<PathBuf> = if args.positional().is_empty() {
+        samples::crates_with_samples(&workspace::current_root()?)
+    } else {
+        args.positional().iter().map(PathBuf::from).collect()
+    };
+
+    let (mut passed, mut total) = (0usize, 0usize);
+    for crate_dir in crates {
+        let runs = samples::plan(&crate_dir, if release { "release" } else { "debug" })?;
+        if runs.is_empty() {
+            eprintln!("{}: no samples found", crate_dir.display());
+            continue;
+        }
+        samples::build(&crate_dir, release)?;
+        for mut run in runs {
+            if let Some(comparison) = override_comparison {
+                run.comparison = comparison;
+            }
+            for result in run.run(timeout)? {
+                total += 1;
+                let millis = result.elapsed.as_millis();
+                match result.verdict {
+                    Verdict::Accepted => {
+                        passed += 1;
+                        println!("{} {}: ok ({} ms)", run.label, result.case.name, millis);
+                    }
+                    Verdict::WrongAnswer(diff) => {
+                        println!(
+                            "{} {}: WRONG ANSWER ({} ms)",
+                            run.label, result.case.name, millis
+                        );
+                        print!("{}", diff);
+                    }
+                    Verdict::RuntimeError(stderr) => {
+                        println!("{} {}: RUN TIME ERROR", run.label, result.case.name);
+                        for line in stderr.lines() {
+                            println!("  {}", line);
+                        }
+                    }
+                    Verdict::TimeLimitExceeded => {
+                        println!("{} {}: TIME LIMIT EXCEEDED", run.label, result.case.name);
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{}/{} samples passed", passed, total);
+    if passed == total {
+        Ok(())
+    } else {
+        Err(format!("{} samples failed", total - passed))
+    }
+}
+
+fn main() {
+    finish(run());
+}
@@ -0,0 +1,270 @@
+/// Turns a solution crate into a single file that Kattis (or plain `rustc`) can compile.
+/// Every `import!(name)` becomes an inline `mod name { ... }` holding `libfreuden/src/name.rs`,
+/// the `import!` macro itself is dropped, and anything that only exists under `cfg(test)` is
+/// stripped along the way. Unless asked not to, library items the solution never reaches are
+/// pruned so the submission stays small.
+use crate::prune::{Usage, prune};
+use crate::source::{Item, ItemKind, SourceFile};
+use crate::workspace::SolutionTarget;
+use std::path::Path;
+
+pub fn bundle(
+    target: &SolutionTarget,
+    libfreuden_src: &Path,
+    prune_unused: bool,
+) -> Result<String, String> {
+    let solution = read_source(&target.source)?;
+    let origin = target.source.display().to_string();
+    let local_dir = target.source.parent().unwrap();
+    bundle_source(&solution, &origin, local_dir, libfreuden_src, prune_unused)
+}
+
+/// Bundles solution code that is already in memory. `origin` names it in the header comment and
+/// `local_dir` is where its own `mod name;` files live.
+pub fn bundle_source(
+    solution: &SourceFile,
+    origin: &str,
+    local_dir: &Path,
+    libfreuden_src: &Path,
+    prune_unused: bool,
+) -> Result<String, String> {
+    let mut imports = Vec::<String>::new();
+    let mut body = String::new();
+    for item in &solution.items {
+        if solution.is_test_only(item) {
+            continue;
+        }
+        match (item.kind, item.name.as_deref()) {
+            (ItemKind::MacroRules, Some("import")) => {}
+            (ItemKind::MacroCall, Some("import")) => {
+                let module = *solution
+                    .macro_arguments(item)
+                    .first()
+                    .ok_or("import! needs a module name")?;
+                if !imports.iter().any(|imported| imported == module) {
+                    imports.push(module.to_string());
+                }
+                body.push_str(leading_trivia(solution, item));
+                body.push_str(&use_line(module));
+            }
+            (ItemKind::Mod, Some(module)) if solution.item_code(item).ends_with(';') => {
+                let module_source = read_local_module(local_dir, module)?;
+                let code = solution.item_code(item);
+                body.push_str(leading_trivia(solution, item));
+                body.push_str(&code[..code.len() - 1]);
+                body.push_str(" {\n");
+                body.push_str(&strip_test_items(&module_source));
+                body.push_str("\n}");
+            }
+            _ => body.push_str(solution.item_text(item)),
+        }
+    }
+
+    let modules = resolve_modules(&imports, libfreuden_src)?;
+    let module_texts = if prune_unused {
+        let roots = Usage::of_source(&SourceFile::parse(body.clone())?);
+        prune(&roots, &modules)
+    } else {
+        modules
+            .iter()
+            .map(|(name, source)| {
+                let text = strip_test_items(source).trim_matches('\n').to_string();
+                (name.clone(), text)
+            })
+            .collect()
+    };
+
+    let mut bundled = format!(
+        "// Single-file submission bundled from {} with libfreuden inlined\n",
+        origin
+    );
+    for (module, _) in module_texts.iter().filter(|(_, text)| text.is_empty()) {
+        body = body.replace(&use_line(module), "");
+    }
+    bundled.push_str(body.trim_start_matches('\n'));
+    bundled.push('\n');
+    for (module, text) in module_texts.iter().filter(|(_, text)| !text.is_empty()) {
+        bundled.push_str(&format!("\nmod {} {{\n", module));
+        bundled.push_str(text);
+        bundled.push_str("\n}\n");
+    }
+    Ok(bundled)
+}
+
+fn use_line(module: &str) -> String {
+    format!("#[allow(unused_imports)]\nuse {}::*;", module)
+}
+
+/// Compiles a bundled file with plain `rustc`, returning the compiler output on failure
+pub fn check(bundled: &Path, edition: &str) -> Result<(), String> {
+    let out_dir = std::env::temp_dir().join(format!("kattis_bundle_check_{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).map_err(|error| error.to_string())?;
+    let output = std::process::Command::new("rustc")
+        .arg("--edition")
+        .arg(edition)
+        .arg("--crate-type")
+        .arg("bin")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(bundled)
+        .output()
+        .map_err(|error| format!("failed to run rustc: {}", error))?;
+    let _ = std::fs::remove_dir_all(&out_dir);
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+pub fn read_source(path: &Path) -> Result<SourceFile, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    SourceFile::parse(text).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// The libfreuden modules to inline, in order, including those reached through `crate::name`
+fn resolve_modules(
+    imports: &[String],
+    libfreuden_src: &Path,
+) -> Result<Vec<(String, SourceFile)>, String> {
+    let mut modules: Vec<(String, SourceFile)> = Vec::new();
+    let mut pending: Vec<String> = imports.to_vec();
+    pending.reverse();
+    while let Some(module) = pending.pop() {
+        if modules.iter().any(|(name, _)| *name == module) {
+            continue;
+        }
+        let path = libfreuden_src.join(format!("{}.rs", module));
+        if !path.is_file() {
+            return Err(format!(
+                "import!({}) has no libfreuden/src/{}.rs",
+                module, module
+            ));
+        }
+        let source = read_source(&path)?;
+        for dependency in crate_references(&source) {
+            if libfreuden_src.join(format!("{}.rs", dependency)).is_file() {
+                pending.push(dependency);
+            }
+        }
+        modules.push((module, source));
+    }
+    Ok(modules)
+}
+
+/// Names `x` appearing as `crate::x` or `super::x` outside of test-only items
+fn crate_references(source: &SourceFile) -> Vec<String> {
+    let mut references = Vec::new();
+    for item in source
+        .items
+        .iter()
+        .filter(|item| !source.is_test_only(item))
+    {
+        for index in item.header..item.tokens.end.saturating_sub(3) {
+            if matches!(source.token_text(index), "crate" | "super")
+                && source.token_text(index + 1) == ":"
+                && source.token_text(index + 2) == ":"
+            {
+                references.push(source.token_text(index + 3).to_string());
+            }
+        }
+    }
+    references
+}
+
+fn read_local_module(dir: &Path, module: &str) -> Result<SourceFile, String> {
+    let flat = dir.join(format!("{}.rs", module));
+    if flat.is_file() {
+        read_source(&flat)
+    } else {
+        read_source(&dir.join(module).join("mod.rs"))
+    }
+}
+
+fn leading_trivia<'a>(source: &'a SourceFile, item: &Item) -> &'a str {
+    &source.text[item.span.start..source.tokens[item.tokens.start].start]
+}
+
+/// The source with every `#[test]`, `#[bench]` and `#[cfg(test)]` item removed
+pub fn strip_test_items(source: &SourceFile) -> String {
+    source
+        .items
+        .iter()
+        .filter(|item| !source.is_test_only(item))
+        .map(|item| source.item_text(item))
+        .collect()
+}
+
+#[cfg(test)]
+mod bundle_tests {
+    use super::*;
+
+    fn workspace_crate(path: &str) -> SolutionTarget {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        SolutionTarget::resolve(&root.join(path), None).unwrap()
+    }
+
+    fn libfreuden_src() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("libfreuden/src")
+    }
+
+    #[test]
+    fn test_strip_test_items() {
+        let source = SourceFile::parse(
+            "fn keep() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn t() {}\n}\n"
+                .to_string(),
+        )
+        .unwrap();
+        assert_eq!(strip_test_items(&source), "fn keep() {}");
+    }
+
+    #[test]
+    fn test_bundle_setnja() {
+        let bundled = bundle(
+            &workspace_crate("completed/hard/setnja"),
+            &libfreuden_src(),
+            false,
+        )
+        .unwrap();
+        assert!(!bundled.contains("import!"));
+        assert!(!bundled.contains("macro_rules! import"));
+        assert!(!bundled.contains("setnja_tests"));
+        assert!(bundled.contains("use input::*;"));
+        assert!(bundled.contains("mod mediocre_bigint {\n"));
+        assert!(bundled.contains("fn main()"));
+    }
+
+    #[test]
+    fn test_bundle_without_imports_is_unchanged() {
+        let bundled = bundle(
+            &workspace_crate("completed/trivial/telja"),
+            &libfreuden_src(),
+            true,
+        )
+        .unwrap();
+        let original = std::fs::read_to_string(
+            libfreuden_src().join("../../completed/trivial/telja/src/main.rs"),
+        )
+        .unwrap();
+        assert!(bundled.ends_with(&original));
+    }
+
+    #[test]
+    fn test_bundle_prunes_unused_library_items() {
+        let bundled = bundle(
+            &workspace_crate("completed/hard/setnja"),
+            &libfreuden_src(),
+            true,
+        )
+        .unwrap();
+        assert!(bundled.contains("pub fn read_str()"));
+        assert!(!bundled.contains("pub fn read_vec<"));
+        assert!(!bundled.contains("macro_rules! kattis_struct"));
+        assert!(bundled.contains("impl std::ops::MulAssign<&MediocreBigint> for MediocreBigint"));
+        assert!(!bundled.contains("impl std::ops::DivAssign"));
+    }
+}
@@ -0,0 +1,86 @@
+/// Minimal command line handling shared by the tool binaries.
+pub struct Args {
+    positional: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    /// Parses `--name value` for names in `value_options`, `--name` flags, and positional arguments
+    pub fn parse<I: Iterator<Item = String>>(
+        args: I,
+        value_options: &[&str],
+    ) -> Result<Args, String> {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                if let Some((name, value)) = name.split_once('=') {
+                    options.push((name.to_string(), Some(value.to_string())));
+                } else if value_options.contains(&name) {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("--{} needs a value", name))?;
+                    options.push((name.to_string(), Some(value)));
+                } else {
+                    options.push((name.to_string(), None));
+                }
+            } else {
+                positional.push(arg);
+            }
+        }
+        Ok(Args {
+            positional,
+            options,
+        })
+    }
+
+    pub fn from_env(value_options: &[&str]) -> Result<Args, String> {
+        Args::parse(std::env::args().skip(1), value_options)
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+/// Prints the error and exits non-zero, so every binary reports failures the same way
+pub fn finish(result: Result<(), String>) {
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let args = Args::parse(
+            ["crate", "--bin", "fenwick", "--check", "--output=out.rs"]
+                .iter()
+                .map(|s| s.to_string()),
+            &["bin", "output"],
+        )
+        .unwrap();
+        assert_eq!(args.positional(), ["crate".to_string()]);
+        assert_eq!(args.value("bin"), Some("fenwick"));
+        assert_eq!(args.value("output"), Some("out.rs"));
+        assert!(args.flag("check"));
+        assert!(!args.flag("quiet"));
+    }
+}
@@ -0,0 +1,206 @@
+/// Pulls one solution out of a `combined_*` crate as a standalone program.
+/// The problem function becomes `main`, and of the rest of the file only the helpers it reaches
+/// (functions, types, traits, local macros) are kept. The result then goes through the bundler
+/// like any other solution, so the libfreuden items it uses are inlined too.
+use crate::bundle::{bundle_source, read_source};
+use crate::prune::{Usage, prune};
+use crate::source::{ItemKind, SourceFile, TokenKind};
+use std::path::{Path, PathBuf};
+
+/// The crates whose `PROBLEMS` tables `extract` searches
+pub const COMBINED_CRATES: [&str; 2] = [
+    "completed/trivial/combined_trivial",
+    "completed/medium/combined_medium",
+];
+
+/// The `(problem id, function)` pairs in a combined crate's `PROBLEMS` table
+pub fn registry(source: &SourceFile) -> Vec<(String, String)> {
+    let Some(table) = source
+        .items
+        .iter()
+        .find(|item| item.kind == ItemKind::Const && item.name.as_deref() == Some("PROBLEMS"))
+    else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    for index in table.header..table.tokens.end.saturating_sub(4) {
+        if source.token_text(index) == "("
+            && source.tokens[index + 1].kind == TokenKind::Literal
+            && source.token_text(index + 2) == ","
+            && source.tokens[index + 3].kind == TokenKind::Ident
+        {
+            let id = source.token_text(index + 1).trim_matches('"');
+            entries.push((id.to_string(), source.token_text(index + 3).to_string()));
+        }
+    }
+    entries
+}
+
+/// Finds the crate and function for a problem id (or a function name that is not registered)
+pub fn find_problem(crate_dirs: &[PathBuf], problem: &str) -> Result<(PathBuf, String), String> {
+    for crate_dir in crate_dirs {
+        let source = read_source(&crate_dir.join("src").join("main.rs"))?;
+        if let Some((_, function)) = registry(&source).into_iter().find(|(id, _)| id == problem) {
+            return Ok((crate_dir.clone(), function));
+        }
+        if source
+            .items
+            .iter()
+            .any(|item| item.kind == ItemKind::Fn && item.name.as_deref() == Some(problem))
+        {
+            return Ok((crate_dir.clone(), problem.to_string()));
+        }
+    }
+    Err(format!("no combined crate has a solution for {}", problem))
+}
+
+/// The combined file reduced to `function` renamed to `main` and the local items it reaches,
+/// still using `import!` for libfreuden
+pub fn standalone(combined: &SourceFile, function: &str) -> Result<String, String> {
+    let mut prelude = String::new();
+    let mut local = String::new();
+    let mut found = false;
+    for item in &combined.items {
+        if combined.is_test_only(item) {
+            continue;
+        }
+        match (item.kind, item.name.as_deref()) {
+            // The file-wide `#![allow(dead_code)]` and the dispatch in `main` have no use here
+            (ItemKind::InnerAttribute, _)
+            | (ItemKind::Fn, Some("main" | "run_problem"))
+            | (ItemKind::Const, Some("PROBLEMS")) => {}
+            (ItemKind::MacroRules, Some("import")) | (ItemKind::MacroCall, Some("import")) => {
+                // Skip the doc comments describing the combined file
+                let first = (item.tokens.start..item.header)
+                    .find(|&index| combined.tokens[index].kind != TokenKind::DocComment)
+                    .unwrap_or(item.header);
+                let end = combined.tokens[item.tokens.end - 1].end;
+                prelude.push_str(&combined.text[combined.tokens[first].start..end]);
+                prelude.push_str("\n\n");
+            }
+            (ItemKind::Fn, Some(name)) if name == function => {
+                let name_token = (item.header..item.tokens.end)
+                    .find(|&index| combined.token_text(index) == function)
+                    .map(|index| combined.tokens[index])
+                    .unwrap();
+                local.push_str(&combined.text[item.span.start..name_token.start]);
+                local.push_str("main");
+                local.push_str(&combined.text[name_token.end..item.span.end]);
+                found = true;
+            }
+            _ => local.push_str(combined.item_text(item)),
+        }
+    }
+    if !found {
+        return Err(format!("there is no fn {}", function));
+    }
+
+    let mut roots = Usage::default();
+    roots.idents.insert("main".to_string());
+    let modules = vec![("local".to_string(), SourceFile::parse(local)?)];
+    let (_, kept) = prune(&roots, &modules).remove(0);
+    Ok(format!("{}{}\n", prelude, kept))
+}
+
+/// A single-file submission for `function` from the combined crate at `crate_dir`
+pub fn extract(
+    crate_dir: &Path,
+    function: &str,
+    libfreuden_src: &Path,
+    prune_unused: bool,
+) -> Result<String, String> {
+    let main = crate_dir.join("src").join("main.rs");
+    let combined = read_source(&main)?;
+    let solution = SourceFile::parse(standalone(&combined, function)?)?;
+    let origin = format!(
+        "fn {} in {}",
+        function,
+        Path::new(crate_dir.file_name().unwrap_or_default())
+            .join("src")
+            .join("main.rs")
+            .display()
+    );
+    bundle_source(
+        &solution,
+        &origin,
+        main.parent().unwrap(),
+        libfreuden_src,
+        prune_unused,
+    )
+}
+
+#[cfg(test)]
+mod extract_tests {
+    use super::*;
+
+    fn workspace_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf()
+    }
+
+    fn combined(path: &str) -> SourceFile {
+        read_source(&workspace_root().join(path).join("src/main.rs")).unwrap()
+    }
+
+    #[test]
+    fn test_registry() {
+        let source = SourceFile::parse(
+            "const PROBLEMS: &[(&str, fn())] = &[\n    (\"13floors\", _13floors),\n    (\"skak\", skak),\n];\n"
+                .to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            registry(&source),
+            [("13floors", "_13floors"), ("skak", "skak")]
+                .map(|(id, function)| (id.to_string(), function.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_find_problem() {
+        let crates: Vec<PathBuf> = COMBINED_CRATES
+            .iter()
+            .map(|path| workspace_root().join(path))
+            .collect();
+        assert_eq!(
+            find_problem(&crates, "13floors").unwrap(),
+            (crates[0].clone(), "_13floors".to_string())
+        );
+        assert_eq!(
+            find_problem(&crates, "longestcommonsubsequence_wrong").unwrap(),
+            (
+                crates[1].clone(),
+                "longestcommonsubsequence_wrong".to_string()
+            )
+        );
+        assert!(find_problem(&crates, "nosuchproblem").is_err());
+    }
+
+    #[test]
+    fn test_standalone_keeps_local_helpers() {
+        let medium = combined("completed/medium/combined_medium");
+        let repeatedsubsequence = standalone(&medium, "repeatedsubsequence").unwrap();
+        assert!(repeatedsubsequence.contains("fn main() {"));
+        assert!(repeatedsubsequence.contains("fn find_longest_subsequence("));
+        assert!(!repeatedsubsequence.contains("fn repeatedsubsequence"));
+        assert!(!repeatedsubsequence.contains("fn longest_common_subsequence("));
+        assert!(!repeatedsubsequence.contains("PROBLEMS"));
+        assert!(repeatedsubsequence.starts_with("#[allow(unused)]\nmacro_rules! import"));
+
+        let remoatseating = standalone(&medium, "remoatseating").unwrap();
+        assert!(remoatseating.contains("import!(big_rational);"));
+        assert!(!remoatseating.contains("fn find_longest_subsequence("));
+
+        let europeantrip = standalone(&medium, "europeantrip").unwrap();
+        assert!(europeantrip.contains("macro_rules! kattis_struct"));
+        assert!(!europeantrip.contains("fn find_longest_subsequence("));
+
+        let trivial = combined("completed/trivial/combined_trivial");
+        let batterup = standalone(&trivial, "batterup").unwrap();
+        assert!(batterup.contains("trait UpgradedIterator"));
+        assert!(batterup.contains("impl<T: ?Sized> UpgradedIterator for T"));
+        assert!(standalone(&trivial, "nosuchproblem").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+pub mod bundle;
+pub mod cli;
+pub mod extract;
+pub mod manifest;
+pub mod package;
+pub mod promote;
+pub mod prune;
+pub mod samples;
+pub mod source;
+pub mod workspace;
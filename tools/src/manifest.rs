@@ -0,0 +1,69 @@
+/// Just enough TOML to read and extend the workspace's own Cargo.toml files.
+/// Only `[table]` headers and single-line `key = value` pairs are understood.
+pub struct Manifest {
+    entries: Vec<(String, String, String)>,
+}
+
+impl Manifest {
+    pub fn read(path: &std::path::Path) -> Result<Manifest, String> {
+        std::fs::read_to_string(path)
+            .map(|text| Manifest::parse(&text))
+            .map_err(|error| format!("failed to read {}: {}", path.display(), error))
+    }
+
+    pub fn parse(text: &str) -> Manifest {
+        let mut entries = Vec::new();
+        let mut table = String::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                table = header.trim().to_string();
+            } else if let Some((key, value)) = line.split_once('=') {
+                entries.push((
+                    table.clone(),
+                    key.trim().to_string(),
+                    value.trim().to_string(),
+                ));
+            }
+        }
+        Manifest { entries }
+    }
+
+    /// The raw value of `key` in `[table]`, with surrounding quotes removed from strings
+    pub fn get(&self, table: &str, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(t, k, _)| t == table && k == key)
+            .map(|(_, _, value)| value.trim_matches('"'))
+    }
+
+    pub fn package_name(&self) -> Option<&str> {
+        self.get("package", "name")
+    }
+
+    pub fn edition(&self) -> &str {
+        self.get("package", "edition").unwrap_or("2021")
+    }
+}
+
+#[cfg(test)]
+mod manifest_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse(
+            "[package]\nname = \"setnja\"\nedition = \"2024\"\n\n[features]\ndefault = [\"libfreuden\"]\n",
+        );
+        assert_eq!(manifest.package_name(), Some("setnja"));
+        assert_eq!(manifest.edition(), "2024");
+        assert_eq!(
+            manifest.get("features", "default"),
+            Some("[\"libfreuden\"]")
+        );
+        assert_eq!(manifest.get("package", "default"), None);
+    }
+}
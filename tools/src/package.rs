@@ -0,0 +1,448 @@
+/// Reads a Kattis problem package from disk and turns it into a new crate under `progress/`.
+///
+/// A package is a directory (or a zip of one) holding `problem.yaml`, the statement, and
+/// `data/sample/*.in` / `*.ans`. Both the legacy and the 2023-07 `problem.yaml` layouts are read,
+/// along with the `.timelimit` file problemtools leaves behind.
+use crate::samples::{self, SampleCase};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemPackage {
+    /// The Kattis problem id, e.g. `hello`
+    pub id: String,
+    pub name: Option<String>,
+    pub difficulty: Option<f64>,
+    /// Seconds
+    pub time_limit: Option<f64>,
+    pub validator_flags: Option<String>,
+    pub samples: Vec<SampleCase>,
+}
+
+impl ProblemPackage {
+    /// Opens a package directory or zip. Zips are unpacked into `scratch`, which must outlive
+    /// the returned package because the samples are read from there.
+    pub fn open(path: &Path, scratch: &Path) -> Result<ProblemPackage, String> {
+        let is_zip = path.extension().is_some_and(|ext| ext == "zip");
+        let (dir, default_id) = if is_zip {
+            unzip(path, scratch)?;
+            let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+            (find_package_root(scratch)?, stem)
+        } else {
+            let dir = find_package_root(path)?;
+            let id = dir
+                .canonicalize()
+                .map_err(|error| format!("failed to resolve {}: {}", dir.display(), error))?
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            (dir, id)
+        };
+        ProblemPackage::read(&dir, &default_id)
+    }
+
+    /// Reads an unpacked package whose directory is `dir`
+    pub fn read(dir: &Path, id: &str) -> Result<ProblemPackage, String> {
+        let yaml_path = dir.join("problem.yaml");
+        let yaml = std::fs::read_to_string(&yaml_path)
+            .map_err(|error| format!("failed to read {}: {}", yaml_path.display(), error))?;
+        let yaml = parse_yaml(&yaml);
+        let get = |key: &str| {
+            yaml.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+                .filter(|value| !value.is_empty())
+        };
+
+        let name = get("name")
+            .or_else(|| get("name.en"))
+            .map(str::to_string)
+            .or_else(|| statement_name(dir));
+        let difficulty = parse_number(get("difficulty"), "difficulty")?;
+        let time_limit = match parse_number(get("limits.time_limit"), "limits.time_limit")? {
+            Some(seconds) => Some(seconds),
+            None => parse_number(
+                std::fs::read_to_string(dir.join(".timelimit"))
+                    .ok()
+                    .as_deref()
+                    .map(str::trim),
+                ".timelimit",
+            )?,
+        };
+        let samples = samples::discover(&dir.join("data").join("sample"));
+        if samples.is_empty() {
+            return Err(format!("{} has no data/sample/*.in files", dir.display()));
+        }
+
+        Ok(ProblemPackage {
+            id: id.to_string(),
+            name,
+            difficulty,
+            time_limit,
+            validator_flags: get("validator_flags").map(str::to_string),
+            samples,
+        })
+    }
+}
+
+/// The package directory is `path` itself or the single directory inside it holding `problem.yaml`
+fn find_package_root(path: &Path) -> Result<PathBuf, String> {
+    if path.join("problem.yaml").is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let nested: Vec<PathBuf> = std::fs::read_dir(path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("problem.yaml").is_file())
+        .collect();
+    match nested.as_slice() {
+        [only] => Ok(only.clone()),
+        [] => Err(format!("{} has no problem.yaml", path.display())),
+        _ => Err(format!("{} holds several problem packages", path.display())),
+    }
+}
+
+fn unzip(zip: &Path, into: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(into).map_err(|error| error.to_string())?;
+    let output = std::process::Command::new("unzip")
+        .arg("-q")
+        .arg("-o")
+        .arg(zip)
+        .arg("-d")
+        .arg(into)
+        .output()
+        .map_err(|error| format!("failed to run unzip: {}", error))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "failed to unzip {}: {}",
+            zip.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn parse_number(value: Option<&str>, what: &str) -> Result<Option<f64>, String> {
+    value
+        .map(|value| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("{} is not a number: {}", what, value))
+        })
+        .transpose()
+}
+
+/// Just enough YAML for `problem.yaml`: nested `key: value` maps, flattened to dotted keys
+/// (`limits.time_limit`). Lists and multi-line values are skipped.
+pub fn parse_yaml(text: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    for line in text.lines() {
+        let content = match line.find(" #") {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let trimmed = content.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let indent = content.len() - trimmed.len();
+        while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+            parents.pop();
+        }
+        let path: Vec<&str> = parents
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .chain([key.trim()])
+            .collect();
+        let value = value.trim();
+        if value.is_empty() {
+            parents.push((indent, key.trim().to_string()));
+        } else {
+            entries.push((path.join("."), unquote(value).to_string()));
+        }
+    }
+    entries
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// The title from `\problemname{...}` in a LaTeX statement, for packages without a name in
+/// `problem.yaml`
+fn statement_name(dir: &Path) -> Option<String> {
+    ["problem_statement", "statement"]
+        .iter()
+        .flat_map(|statement| std::fs::read_dir(dir.join(statement)).into_iter().flatten())
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "tex"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .find_map(|tex| {
+            let start = tex.find("\\problemname{")? + "\\problemname{".len();
+            let end = start + tex[start..].find('}')?;
+            Some(tex[start..end].trim().to_string())
+        })
+}
+
+/// Kattis' difficulty bands, as shown next to the point value on the problem page
+pub fn difficulty_label(difficulty: f64) -> &'static str {
+    if difficulty < 2.8 {
+        "Easy"
+    } else if difficulty < 5.5 {
+        "Medium"
+    } else {
+        "Hard"
+    }
+}
+
+/// A valid cargo package name for a problem id. Cargo rejects names starting with a digit, so
+/// those get a leading underscore, the same way `combined_trivial` names its functions.
+pub fn crate_name(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Creates `progress/<id>` from `templates/new_crate` with the package renamed, the samples
+/// copied to `samples/`, and the problem details under `[package.metadata.kattis]`
+pub fn create_crate(root: &Path, package: &ProblemPackage) -> Result<PathBuf, String> {
+    let crate_dir = root.join("progress").join(&package.id);
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+    let template = root.join("templates").join("new_crate");
+    copy_dir(&template, &crate_dir)?;
+
+    let manifest_path = crate_dir.join("Cargo.toml");
+    let manifest = read(&manifest_path)?;
+    let mut manifest = manifest.replacen(
+        "name = \"new_crate\"",
+        &format!("name = \"{}\"", crate_name(&package.id)),
+        1,
+    );
+    manifest.push_str(&metadata_table(package));
+    write(&manifest_path, &manifest)?;
+
+    let main_path = crate_dir.join("src").join("main.rs");
+    let main = read(&main_path)?;
+    let name = package.name.as_deref().unwrap_or(&package.id);
+    let header = doc_line(name, &package.id, package.difficulty);
+    write(&main_path, &format!("{}\n{}", header, main))?;
+
+    let samples_dir = crate_dir.join("samples");
+    std::fs::create_dir_all(&samples_dir).map_err(|error| error.to_string())?;
+    for case in &package.samples {
+        for (from, extension) in [(&case.input, "in"), (&case.answer, "ans")] {
+            let to = samples_dir.join(format!("{}.{}", case.name, extension));
+            std::fs::copy(from, &to)
+                .map_err(|error| format!("failed to copy {}: {}", from.display(), error))?;
+        }
+    }
+    Ok(crate_dir)
+}
+
+fn metadata_table(package: &ProblemPackage) -> String {
+    let mut table = String::from("\n[package.metadata.kattis]\n");
+    table.push_str(&format!("problem_id = {}\n", toml_string(&package.id)));
+    if let Some(name) = &package.name {
+        table.push_str(&format!("name = {}\n", toml_string(name)));
+    }
+    if let Some(difficulty) = package.difficulty {
+        table.push_str(&format!("difficulty = {:?}\n", difficulty));
+    }
+    if let Some(time_limit) = package.time_limit {
+        table.push_str(&format!("time_limit = {:?}\n", time_limit));
+    }
+    if let Some(flags) = &package.validator_flags {
+        table.push_str(&format!("validator_flags = {}\n", toml_string(flags)));
+    }
+    table
+}
+
+fn toml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The one-line header solutions start with, e.g.
+/// `/// Bowlstack is a "Hard" (6.1 point) problem on Kattis. https://open.kattis.com/problems/bowlstack`
+pub fn doc_line(name: &str, id: &str, difficulty: Option<f64>) -> String {
+    let url = format!("https://open.kattis.com/problems/{}", id);
+    match difficulty {
+        Some(difficulty) => {
+            let label = difficulty_label(difficulty);
+            let article = if label == "Easy" { "an" } else { "a" };
+            format!(
+                "/// {} is {} \"{}\" ({} point) problem on Kattis. {}",
+                name, article, label, difficulty, url
+            )
+        }
+        None => format!("/// {} is a problem on Kattis. {}", name, url),
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(to).map_err(|error| error.to_string())?;
+    let entries = std::fs::read_dir(from)
+        .map_err(|error| format!("failed to read {}: {}", from.display(), error))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if path.is_dir() {
+            if name != "target" {
+                copy_dir(&path, &to.join(name))?;
+            }
+        } else {
+            std::fs::copy(&path, to.join(name))
+                .map_err(|error| format!("failed to copy {}: {}", path.display(), error))?;
+        }
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text)
+        .map_err(|error| format!("failed to write {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod package_tests {
+    use super::*;
+    use crate::manifest::Manifest;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("kattis_package_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_package(dir: &Path, yaml: &str) {
+        std::fs::create_dir_all(dir.join("data/sample")).unwrap();
+        std::fs::write(dir.join("problem.yaml"), yaml).unwrap();
+        std::fs::write(dir.join("data/sample/1.in"), "1 2\n").unwrap();
+        std::fs::write(dir.join("data/sample/1.ans"), "3\n").unwrap();
+        std::fs::write(dir.join("data/sample/2.in"), "5 5\n").unwrap();
+        std::fs::write(dir.join("data/sample/2.ans"), "10\n").unwrap();
+    }
+
+    #[test]
+    fn test_parse_yaml() {
+        let yaml = parse_yaml(
+            "name: 'Hello World!'\nsource: NCPC # comment\nlimits:\n    time_multiplier: 5\n    time_limit: 2.5\nkeywords:\n  - easy\nvalidator_flags: float_tolerance 1e-6\n",
+        );
+        assert_eq!(
+            yaml,
+            [
+                ("name", "Hello World!"),
+                ("source", "NCPC"),
+                ("limits.time_multiplier", "5"),
+                ("limits.time_limit", "2.5"),
+                ("validator_flags", "float_tolerance 1e-6"),
+            ]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(crate_name("hello"), "hello");
+        assert_eq!(crate_name("13floors"), "_13floors");
+        assert_eq!(crate_name("some-problem"), "some_problem");
+    }
+
+    #[test]
+    fn test_read_package() {
+        let dir = scratch("read").join("addition");
+        write_package(&dir, "name:\n  en: Addition\ndifficulty: 1.4\n");
+        std::fs::write(dir.join(".timelimit"), "1\n").unwrap();
+        let package = ProblemPackage::open(&dir, &dir.join("unused")).unwrap();
+        assert_eq!(package.id, "addition");
+        assert_eq!(package.name.as_deref(), Some("Addition"));
+        assert_eq!(package.difficulty, Some(1.4));
+        assert_eq!(package.time_limit, Some(1.0));
+        assert_eq!(package.validator_flags, None);
+        let names: Vec<&str> = package
+            .samples
+            .iter()
+            .map(|case| case.name.as_str())
+            .collect();
+        assert_eq!(names, ["1", "2"]);
+    }
+
+    #[test]
+    fn test_create_crate() {
+        let root = scratch("create");
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        copy_dir(
+            &workspace.join("templates/new_crate"),
+            &root.join("templates/new_crate"),
+        )
+        .unwrap();
+        let dir = root.join("packages").join("3sum");
+        write_package(
+            &dir,
+            "name: \"3 Sum\"\nlimits:\n  time_limit: 3\nvalidator_flags: float_tolerance 1e-6\n",
+        );
+        let mut package = ProblemPackage::open(&dir, &root.join("unused")).unwrap();
+        package.difficulty = Some(6.1);
+
+        let crate_dir = create_crate(&root, &package).unwrap();
+        assert_eq!(crate_dir, root.join("progress/3sum"));
+        let manifest = Manifest::read(&crate_dir.join("Cargo.toml")).unwrap();
+        assert_eq!(manifest.package_name(), Some("_3sum"));
+        assert_eq!(
+            manifest.get("package.metadata.kattis", "problem_id"),
+            Some("3sum")
+        );
+        assert_eq!(
+            manifest.get("package.metadata.kattis", "difficulty"),
+            Some("6.1")
+        );
+        assert_eq!(
+            manifest.get("package.metadata.kattis", "time_limit"),
+            Some("3.0")
+        );
+        let main = std::fs::read_to_string(crate_dir.join("src/main.rs")).unwrap();
+        assert!(main.starts_with(
+            "/// 3 Sum is a \"Hard\" (6.1 point) problem on Kattis. https://open.kattis.com/problems/3sum\n"
+        ));
+        assert_eq!(
+            std::fs::read_to_string(crate_dir.join("samples/2.ans")).unwrap(),
+            "10\n"
+        );
+        assert!(create_crate(&root, &package).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,413 @@
+/// Files a finished crate from `progress/` under `completed/<tier>/`, or folds a tiny
+/// single-function solution into `combined_trivial` / `combined_medium` as `fn <problem id>()`.
+///
+/// The tier comes from the Kattis difficulty recorded in `[package.metadata.kattis]`, falling back
+/// to the `(6.1 point)` in the solution's header comment.
+use crate::manifest::Manifest;
+use crate::package::{self, difficulty_label};
+use crate::source::{ItemKind, SourceFile, TokenKind};
+use std::path::{Path, PathBuf};
+
+/// The `completed/` subdirectory a problem of this difficulty belongs in
+pub fn tier(difficulty: f64) -> &'static str {
+    match difficulty_label(difficulty) {
+        "Easy" => "trivial",
+        "Medium" => "medium",
+        _ => "hard",
+    }
+}
+
+/// The crate's difficulty from its metadata, or from a `(<points> point)` header comment
+pub fn recorded_difficulty(crate_dir: &Path) -> Result<Option<f64>, String> {
+    let manifest = Manifest::read(&crate_dir.join("Cargo.toml"))?;
+    if let Some(difficulty) = manifest.get("package.metadata.kattis", "difficulty") {
+        return difficulty
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("difficulty is not a number: {}", difficulty));
+    }
+    let main = std::fs::read_to_string(crate_dir.join("src").join("main.rs")).unwrap_or_default();
+    Ok(main
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .find_map(|line| {
+            let end = line.find(" point)")?;
+            let start = line[..end].rfind('(')? + 1;
+            line[start..end].parse().ok()
+        }))
+}
+
+/// The Kattis problem id of a crate: `problem_id` from its metadata, or else its directory name
+pub fn problem_id(crate_dir: &Path) -> Result<String, String> {
+    let manifest = Manifest::read(&crate_dir.join("Cargo.toml"))?;
+    match manifest.get("package.metadata.kattis", "problem_id") {
+        Some(id) => Ok(id.to_string()),
+        None => Ok(crate_dir
+            .canonicalize()
+            .map_err(|error| format!("failed to resolve {}: {}", crate_dir.display(), error))?
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()),
+    }
+}
+
+/// Sets `difficulty` under `[package.metadata.kattis]`, adding the table if needed
+pub fn record_difficulty(crate_dir: &Path, difficulty: f64) -> Result<(), String> {
+    let path = crate_dir.join("Cargo.toml");
+    let text = std::fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    let line = format!("difficulty = {:?}", difficulty);
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let mut table = String::new();
+    let mut replaced = false;
+    for existing in lines.iter_mut() {
+        let trimmed = existing.trim();
+        if let Some(header) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = header.trim().to_string();
+        } else if table == "package.metadata.kattis"
+            && trimmed
+                .split('=')
+                .next()
+                .is_some_and(|key| key.trim() == "difficulty")
+        {
+            *existing = line.clone();
+            replaced = true;
+        }
+    }
+    if !replaced {
+        match lines
+            .iter()
+            .position(|l| l.trim() == "[package.metadata.kattis]")
+        {
+            Some(header) => lines.insert(header + 1, line),
+            None => lines.extend([String::new(), "[package.metadata.kattis]".to_string(), line]),
+        }
+    }
+    std::fs::write(&path, lines.join("\n") + "\n")
+        .map_err(|error| format!("failed to write {}: {}", path.display(), error))?;
+
+    // The importer writes a header without points when it did not know the difficulty yet
+    let main_path = crate_dir.join("src").join("main.rs");
+    let Ok(main) = std::fs::read_to_string(&main_path) else {
+        return Ok(());
+    };
+    let Some((first, rest)) = main.split_once('\n') else {
+        return Ok(());
+    };
+    let Some((name, id)) = first.strip_prefix("/// ").and_then(|line| {
+        line.split_once(" is a problem on Kattis. https://open.kattis.com/problems/")
+    }) else {
+        return Ok(());
+    };
+    let header = package::doc_line(name, id, Some(difficulty));
+    std::fs::write(&main_path, format!("{}\n{}", header, rest))
+        .map_err(|error| format!("failed to write {}: {}", main_path.display(), error))
+}
+
+/// Checks that `crate_dir` is a crate directly under `progress/`
+fn check_in_progress(root: &Path, crate_dir: &Path) -> Result<PathBuf, String> {
+    let crate_dir = crate_dir
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve {}: {}", crate_dir.display(), error))?;
+    if crate_dir.parent() != Some(root.join("progress").as_path()) {
+        return Err(format!(
+            "{} is not a crate in progress/",
+            crate_dir.display()
+        ));
+    }
+    Ok(crate_dir)
+}
+
+/// Moves `progress/<name>` to `completed/<tier>/<name>`
+pub fn move_crate(root: &Path, crate_dir: &Path, tier: &str) -> Result<PathBuf, String> {
+    let crate_dir = check_in_progress(root, crate_dir)?;
+    let destination = root
+        .join("completed")
+        .join(tier)
+        .join(crate_dir.file_name().unwrap());
+    if destination.exists() {
+        return Err(format!("{} already exists", destination.display()));
+    }
+    std::fs::rename(&crate_dir, &destination).map_err(|error| {
+        format!(
+            "failed to move {} to {}: {}",
+            crate_dir.display(),
+            destination.display(),
+            error
+        )
+    })?;
+    Ok(destination)
+}
+
+/// The combined crate that collects the single-function solutions of a tier
+pub fn combined_crate(root: &Path, tier: &str) -> Result<PathBuf, String> {
+    match tier {
+        "trivial" | "medium" => Ok(root
+            .join("completed")
+            .join(tier)
+            .join(format!("combined_{}", tier))),
+        _ => Err(format!("there is no combined crate for {} problems", tier)),
+    }
+}
+
+/// A solution's `main` rewritten as a named function for a combined crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedFunction {
+    pub text: String,
+    /// The libfreuden modules the solution pulls in with `import!`
+    pub imports: Vec<String>,
+}
+
+/// Turns a solution consisting of only `fn main`, `use` lines and `import!`s into `fn name()`.
+/// Doc comments anywhere in the file, like the Kattis header line, end up on the new function.
+pub fn merged_function(solution: &SourceFile, name: &str) -> Result<MergedFunction, String> {
+    let mut docs = Vec::new();
+    let mut uses = Vec::new();
+    let mut imports = Vec::new();
+    let mut main = None;
+    let mut others = Vec::new();
+    for item in &solution.items {
+        match (item.kind, item.name.as_deref()) {
+            (ItemKind::MacroRules, Some("import")) => {}
+            (ItemKind::MacroCall, Some("import")) => {
+                imports.extend(solution.macro_arguments(item).iter().map(|m| m.to_string()));
+            }
+            (ItemKind::Use, _) => uses.push(solution.item_code(item).to_string()),
+            (ItemKind::Fn, Some("main")) => {
+                main = Some(item);
+                continue;
+            }
+            (_, item_name) => others.push(item_name.unwrap_or("?").to_string()),
+        }
+        docs.extend(
+            (item.tokens.start..item.header)
+                .filter(|&index| solution.tokens[index].kind == TokenKind::DocComment)
+                .map(|index| solution.token_text(index).to_string()),
+        );
+    }
+    if !others.is_empty() {
+        return Err(format!(
+            "only single-function solutions can be merged, this one also has {}",
+            others.join(", ")
+        ));
+    }
+    let main = main.ok_or("the solution has no fn main")?;
+
+    let leading = solution.text[main.span.start..solution.tokens[main.tokens.start].start]
+        .trim_start_matches('\n');
+    let code_start = solution.tokens[main.tokens.start].start;
+    let name_index = (main.header..main.tokens.end)
+        .find(|&index| solution.token_text(index) == "main")
+        .unwrap();
+    let body_index = (name_index..main.tokens.end)
+        .find(|&index| solution.token_text(index) == "{")
+        .ok_or("fn main has no body")?;
+    let name_token = solution.tokens[name_index];
+    let body_open = solution.tokens[body_index].end;
+
+    let mut text = String::new();
+    for doc in docs {
+        text.push_str(&doc);
+        text.push('\n');
+    }
+    text.push_str(leading);
+    text.push_str(&solution.text[code_start..name_token.start]);
+    text.push_str(name);
+    text.push_str(&solution.text[name_token.end..body_open]);
+    for use_line in uses {
+        text.push_str("\n    ");
+        text.push_str(&use_line);
+    }
+    text.push_str(&solution.text[body_open..solution.tokens[main.tokens.end - 1].end]);
+    Ok(MergedFunction { text, imports })
+}
+
+/// Adds the function to a combined crate's source, after the last solution and registered under
+/// its problem id in `PROBLEMS`, along with any `import!` it is missing
+pub fn merge_into(
+    combined: &SourceFile,
+    function: &MergedFunction,
+    name: &str,
+    problem_id: &str,
+) -> Result<String, String> {
+    if combined
+        .items
+        .iter()
+        .any(|item| item.kind == ItemKind::Fn && item.name.as_deref() == Some(name))
+    {
+        return Err(format!("there already is a fn {}", name));
+    }
+    let import_items: Vec<_> = combined
+        .items
+        .iter()
+        .filter(|item| item.kind == ItemKind::MacroCall && item.name.as_deref() == Some("import"))
+        .collect();
+    let missing: String = function
+        .imports
+        .iter()
+        .filter(|module| {
+            !import_items
+                .iter()
+                .any(|item| combined.macro_arguments(item).contains(&module.as_str()))
+        })
+        .map(|module| format!("\nimport!({});", module))
+        .collect();
+
+    // Edits go from the end of the file backwards so earlier offsets stay valid
+    let mut text = combined.text.clone();
+    let registry = combined
+        .items
+        .iter()
+        .find(|item| item.kind == ItemKind::Const && item.name.as_deref() == Some("PROBLEMS"));
+    match registry {
+        Some(registry) => {
+            let close = combined.tokens[registry.tokens.end - 2].start;
+            let entry_at = text[..close]
+                .rfind('\n')
+                .map_or(close, |newline| newline + 1);
+            text.insert_str(entry_at, &format!("    (\"{}\", {}),\n", problem_id, name));
+            text.insert_str(registry.span.start, &format!("\n\n{}", function.text));
+        }
+        None => {
+            text = format!("{}\n\n{}\n", text.trim_end_matches('\n'), function.text);
+        }
+    }
+    if !missing.is_empty() {
+        let after = match import_items.last() {
+            Some(item) => item.span.end,
+            None => return Err("the combined crate has no import! to add modules after".into()),
+        };
+        text.insert_str(after, &missing);
+    }
+    Ok(text)
+}
+
+/// Merges the solution in `crate_dir` into the tier's combined crate, moves its samples to
+/// `samples/<problem id>/` there, and removes the crate. The combined crate is rebuilt first and
+/// left untouched if the merged code does not compile.
+pub fn merge(root: &Path, crate_dir: &Path, tier: &str) -> Result<PathBuf, String> {
+    let crate_dir = check_in_progress(root, crate_dir)?;
+    let combined_dir = combined_crate(root, tier)?;
+    let id = problem_id(&crate_dir)?;
+    let name = package::crate_name(&id);
+
+    let solution = crate::bundle::read_source(&crate_dir.join("src").join("main.rs"))?;
+    let function = merged_function(&solution, &name)?;
+    let combined_path = combined_dir.join("src").join("main.rs");
+    let combined = crate::bundle::read_source(&combined_path)?;
+    let merged = merge_into(&combined, &function, &name, &id)?;
+
+    let samples_dir = combined_dir.join("samples").join(&id);
+    if samples_dir.exists() {
+        return Err(format!("{} already exists", samples_dir.display()));
+    }
+    std::fs::write(&combined_path, merged)
+        .map_err(|error| format!("failed to write {}: {}", combined_path.display(), error))?;
+    if let Err(error) = crate::samples::build(&combined_dir, false) {
+        let _ = std::fs::write(&combined_path, &combined.text);
+        return Err(error);
+    }
+
+    let cases = crate::samples::discover(&crate_dir.join("samples"));
+    std::fs::create_dir_all(&samples_dir).map_err(|error| error.to_string())?;
+    for case in &cases {
+        for path in [&case.input, &case.answer] {
+            let to = samples_dir.join(path.file_name().unwrap());
+            std::fs::copy(path, &to)
+                .map_err(|error| format!("failed to copy {}: {}", path.display(), error))?;
+        }
+    }
+    std::fs::remove_dir_all(&crate_dir)
+        .map_err(|error| format!("failed to remove {}: {}", crate_dir.display(), error))?;
+    Ok(combined_dir)
+}
+
+#[cfg(test)]
+mod promote_tests {
+    use super::*;
+
+    fn source(text: &str) -> SourceFile {
+        SourceFile::parse(text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_tier() {
+        assert_eq!(tier(1.4), "trivial");
+        assert_eq!(tier(2.8), "medium");
+        assert_eq!(tier(5.2), "medium");
+        assert_eq!(tier(6.1), "hard");
+    }
+
+    #[test]
+    fn test_merged_function() {
+        let solution = source(
+            "/// Hello is an \"Easy\" (1.2 point) problem on Kattis. https://open.kattis.com/problems/hello\n// noinspection\nmacro_rules! import {($name:ident) => {};}\n\nimport!(input);\nuse std::io::Write;\n\n// Prints the greeting\nfn main() {\n    println!(\"{}\", read_str());\n}\n",
+        );
+        let function = merged_function(&solution, "hello").unwrap();
+        assert_eq!(
+            function.text,
+            "/// Hello is an \"Easy\" (1.2 point) problem on Kattis. https://open.kattis.com/problems/hello\n// Prints the greeting\nfn hello() {\n    use std::io::Write;\n    println!(\"{}\", read_str());\n}"
+        );
+        assert_eq!(function.imports, ["input"]);
+
+        let helpers = source("fn helper() {}\nfn main() {\n    helper();\n}\n");
+        assert!(merged_function(&helpers, "hello").is_err());
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let combined = source(
+            "macro_rules! import {($name:ident) => {};}\n\nimport!(input);\n\nfn main() {\n    bergmal();\n}\n\nfn bergmal() {}\n\n/// Every solution\nconst PROBLEMS: &[(&str, fn())] = &[\n    (\"bergmal\", bergmal),\n];\n\nfn run_problem() {}\n",
+        );
+        let function = MergedFunction {
+            text: "fn _13floors() {}".to_string(),
+            imports: vec!["input".to_string(), "mediocre_bigint".to_string()],
+        };
+        assert_eq!(
+            merge_into(&combined, &function, "_13floors", "13floors").unwrap(),
+            "macro_rules! import {($name:ident) => {};}\n\nimport!(input);\nimport!(mediocre_bigint);\n\nfn main() {\n    bergmal();\n}\n\nfn bergmal() {}\n\nfn _13floors() {}\n\n/// Every solution\nconst PROBLEMS: &[(&str, fn())] = &[\n    (\"bergmal\", bergmal),\n    (\"13floors\", _13floors),\n];\n\nfn run_problem() {}\n"
+        );
+        assert!(merge_into(&combined, &function, "bergmal", "bergmal").is_err());
+
+        let unregistered = source("fn main() {}\n");
+        let function = MergedFunction {
+            text: "fn _13floors() {}".to_string(),
+            imports: Vec::new(),
+        };
+        assert_eq!(
+            merge_into(&unregistered, &function, "_13floors", "13floors").unwrap(),
+            "fn main() {}\n\nfn _13floors() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_recorded_difficulty() {
+        let dir = std::env::temp_dir().join(format!("kattis_promote_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(
+            dir.join("src/main.rs"),
+            "/// X is a problem on Kattis. https://open.kattis.com/problems/x\nfn main() {}\n",
+        )
+        .unwrap();
+        assert_eq!(recorded_difficulty(&dir).unwrap(), None);
+
+        record_difficulty(&dir, 4.5).unwrap();
+        assert_eq!(recorded_difficulty(&dir).unwrap(), Some(4.5));
+        record_difficulty(&dir, 2.0).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"x\"\n\n[package.metadata.kattis]\ndifficulty = 2.0\n"
+        );
+        assert!(
+            std::fs::read_to_string(dir.join("src/main.rs"))
+                .unwrap()
+                .starts_with("/// X is a \"Medium\" (4.5 point) problem on Kattis.")
+        );
+
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        assert_eq!(recorded_difficulty(&dir).unwrap(), Some(4.5));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,747 @@
+/// Item-level dead code removal for inlined libfreuden modules.
+/// Starting from the identifiers and operators used by the solution, items are kept only when
+/// something live names them: functions, types, constants and macros by name, inherent methods by
+/// name, and operator trait impls only when their operator shows up in live code.
+/// Solution code is taken at its word, so any `/` keeps `Div` impls alive. Library code is held
+/// to a stricter standard: an operator only counts when one side is `self`, a library type, or a
+/// variable bound to one.
+use crate::source::{Item, ItemKind, SourceFile, TokenKind};
+use std::collections::HashSet;
+
+/// Multi-character punctuation, longest first, so `a /= b` reads as `/=` rather than `/` and `=`
+const COMPOUND_PUNCTUATION: [&str; 24] = [
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=",
+    "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>", "..",
+];
+
+/// Operator traits, the operator that invokes them, and their method name
+const OPERATOR_TRAITS: [(&str, &str, &str); 24] = [
+    ("Add", "+", "add"),
+    ("AddAssign", "+=", "add_assign"),
+    ("Sub", "-", "sub"),
+    ("SubAssign", "-=", "sub_assign"),
+    ("Mul", "*", "mul"),
+    ("MulAssign", "*=", "mul_assign"),
+    ("Div", "/", "div"),
+    ("DivAssign", "/=", "div_assign"),
+    ("Rem", "%", "rem"),
+    ("RemAssign", "%=", "rem_assign"),
+    ("Neg", "-", "neg"),
+    ("Not", "!", "not"),
+    ("BitAnd", "&", "bitand"),
+    ("BitAndAssign", "&=", "bitand_assign"),
+    ("BitOr", "|", "bitor"),
+    ("BitOrAssign", "|=", "bitor_assign"),
+    ("BitXor", "^", "bitxor"),
+    ("BitXorAssign", "^=", "bitxor_assign"),
+    ("Shl", "<<", "shl"),
+    ("ShlAssign", "<<=", "shl_assign"),
+    ("Shr", ">>", "shr"),
+    ("ShrAssign", ">>=", "shr_assign"),
+    // Only reached through iterator adapters, e.g. `iter.sum()`
+    ("Sum", "", "sum"),
+    ("Product", "", "product"),
+];
+
+/// Everything a piece of code names or uses
+#[derive(Default)]
+pub struct Usage {
+    pub idents: HashSet<String>,
+    /// Identifiers called as `.name` or `Path::name`, which is how operator methods get called
+    pub paths: HashSet<String>,
+    pub operators: HashSet<String>,
+}
+
+/// What library code is known to operate on, so `limb / BIGINT_LIMIT` does not count as a use of
+/// `Div for MediocreBigint`
+struct Typing<'a> {
+    /// Structs and enums defined by the library modules
+    types: &'a HashSet<String>,
+    self_is_typed: bool,
+}
+
+impl Usage {
+    /// Every identifier and operator in the source, without any attempt at typing
+    pub fn of_source(source: &SourceFile) -> Usage {
+        let mut usage = Usage::default();
+        usage.add_tokens(source, 0..source.tokens.len(), None);
+        usage
+    }
+
+    /// Adds the identifiers in `tokens`, and either every operator or, with `typing`, only the
+    /// operators with a library-typed operand
+    fn add_tokens(
+        &mut self,
+        source: &SourceFile,
+        tokens: std::ops::Range<usize>,
+        typing: Option<&Typing>,
+    ) {
+        let typed_variables = typing.map(|typing| typed_variables(source, &tokens, typing));
+        let mut index = tokens.start;
+        while index < tokens.end {
+            let token = source.tokens[index];
+            match token.kind {
+                TokenKind::Ident => {
+                    let ident = source.token_text(index);
+                    let ident = ident.strip_prefix("r#").unwrap_or(ident).to_string();
+                    if index > 0 && matches!(source.token_text(index - 1), "." | ":") {
+                        self.paths.insert(ident.clone());
+                    }
+                    self.idents.insert(ident);
+                }
+                TokenKind::Punct => {
+                    let run = punct_run(source, index, tokens.end);
+                    let operator = COMPOUND_PUNCTUATION
+                        .iter()
+                        .find(|compound| run.starts_with(*compound))
+                        .map_or(&run[..1], |compound| compound);
+                    // `name!` is a macro call, not a negation
+                    let is_macro_bang = operator == "!"
+                        && index > 0
+                        && source.tokens[index - 1].kind == TokenKind::Ident
+                        && source.tokens[index - 1].end == token.start;
+                    let counts = match (typing, &typed_variables) {
+                        (Some(typing), Some(variables)) => {
+                            let after = index + operator.len();
+                            is_typed_operand_before(source, index, tokens.start, variables)
+                                || is_typed_operand_after(
+                                    source, after, tokens.end, variables, typing,
+                                )
+                        }
+                        _ => true,
+                    };
+                    if !is_macro_bang && counts {
+                        self.operators.insert(operator.to_string());
+                    }
+                    index += operator.len() - 1;
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+    }
+}
+
+/// `self` and the parameters and `let` bindings in `tokens` whose type or initializer is a
+/// library type
+fn typed_variables(
+    source: &SourceFile,
+    tokens: &std::ops::Range<usize>,
+    typing: &Typing,
+) -> HashSet<String> {
+    let mut variables = HashSet::new();
+    if typing.self_is_typed {
+        variables.insert("self".to_string());
+    }
+    let is_type = |index: usize| {
+        let text = source.token_text(index);
+        text == "Self" || typing.types.contains(text)
+    };
+
+    for index in tokens.clone() {
+        if source.tokens[index].kind != TokenKind::Ident || index + 1 >= tokens.end {
+            continue;
+        }
+        let name = source.token_text(index);
+
+        // `name: Type` in parameters, `let` bindings and closures
+        if source.token_text(index + 1) == ":"
+            && source.token_text(index + 2) != ":"
+            && (index == 0 || source.token_text(index - 1) != ":")
+        {
+            let end = expression_end(
+                source,
+                index + 2,
+                tokens.end,
+                &[",", ")", "=", ";", "{", "|"],
+            );
+            if (index + 2..end).any(is_type) {
+                variables.insert(name.to_string());
+                continue;
+            }
+        }
+
+        // `let mut name = initializer;`
+        if name == "let" {
+            let mut binding = index + 1;
+            if source.token_text(binding) == "mut" {
+                binding += 1;
+            }
+            let Some(equals) = (binding..tokens.end)
+                .take_while(|&i| source.token_text(i) != ";")
+                .find(|&i| source.token_text(i) == "=")
+            else {
+                continue;
+            };
+            if is_typed_operand_after(source, equals + 1, tokens.end, &variables, typing) {
+                variables.insert(source.token_text(binding).to_string());
+            }
+        }
+    }
+    variables
+}
+
+/// First index at or after `start` holding one of `stops` outside of any brackets
+fn expression_end(source: &SourceFile, start: usize, end: usize, stops: &[&str]) -> usize {
+    let mut depth = 0usize;
+    for index in start..end {
+        let text = source.token_text(index);
+        if depth == 0 && stops.contains(&text) {
+            return index;
+        }
+        match text {
+            "(" | "[" | "{" | "<" => depth += 1,
+            ")" | "]" | "}" | ">" => {
+                if depth == 0 {
+                    return index;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    end
+}
+
+/// Whether the expression ending right before `operator` is a typed variable, possibly followed by
+/// method calls such as `.clone()`
+fn is_typed_operand_before(
+    source: &SourceFile,
+    operator: usize,
+    start: usize,
+    variables: &HashSet<String>,
+) -> bool {
+    let mut index = operator;
+    let mut saw_field = false;
+    while index > start {
+        index -= 1;
+        match source.token_text(index) {
+            ")" | "]" => {
+                let is_index = source.token_text(index) == "]";
+                let mut depth = 0usize;
+                loop {
+                    match source.token_text(index) {
+                        ")" | "]" => depth += 1,
+                        "(" | "[" => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    if index == start {
+                        return false;
+                    }
+                    index -= 1;
+                }
+                saw_field |= is_index;
+                // A parenthesized expression rather than a call
+                if index == start || source.tokens[index - 1].kind != TokenKind::Ident {
+                    return false;
+                }
+                index -= 1;
+                if index > start && source.token_text(index - 1) == "." {
+                    index -= 1;
+                } else {
+                    return false;
+                }
+            }
+            "?" => {}
+            text if source.tokens[index].kind == TokenKind::Ident => {
+                if index > start && source.token_text(index - 1) == "." {
+                    saw_field = true;
+                    index -= 1;
+                } else {
+                    return !saw_field && variables.contains(text);
+                }
+            }
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Whether the expression starting at `start` is a typed variable (with method calls), a path or
+/// struct literal of a library type, or `Self`
+fn is_typed_operand_after(
+    source: &SourceFile,
+    start: usize,
+    end: usize,
+    variables: &HashSet<String>,
+    typing: &Typing,
+) -> bool {
+    let mut index = start;
+    while index < end && matches!(source.token_text(index), "&" | "*" | "-" | "mut" | "(") {
+        index += 1;
+    }
+    if index >= end || source.tokens[index].kind != TokenKind::Ident {
+        return false;
+    }
+    let head = source.token_text(index);
+    if head == "Self" || typing.types.contains(head) {
+        return true;
+    }
+    if !variables.contains(head) {
+        return false;
+    }
+    index += 1;
+    while index + 1 < end && source.token_text(index) == "." {
+        // `.method(` keeps the type as far as this heuristic is concerned, `.field` does not
+        if source.token_text(index + 2) != "(" {
+            return false;
+        }
+        index = expression_end(source, index + 3, end, &[]) + 1;
+    }
+    source.token_text(index) != "["
+}
+
+/// Adjacent punctuation starting at `index`, e.g. `*=` or `->`
+fn punct_run(source: &SourceFile, index: usize, end: usize) -> String {
+    let mut run = source.token_text(index).to_string();
+    let mut next = index + 1;
+    while next < end
+        && source.tokens[next].kind == TokenKind::Punct
+        && source.tokens[next].start == source.tokens[next - 1].end
+    {
+        run.push_str(source.token_text(next));
+        next += 1;
+    }
+    run
+}
+
+/// A keepable piece of a module: a whole item, or one member of an inherent impl
+struct Unit {
+    module: usize,
+    item: usize,
+    member: Option<usize>,
+    /// Name that makes this unit live when something live mentions it
+    name: Option<String>,
+    /// For impls, the type and trait they are for, e.g. `("MediocreBigint", Some("AddAssign"))`
+    self_type: Option<String>,
+    trait_name: Option<String>,
+    /// For traits, the methods they declare, since extension traits are only ever called as
+    /// `.method()`
+    methods: Vec<String>,
+    /// For macro calls, the identifiers in the definition of the macro they call
+    mentions: Vec<String>,
+    live: bool,
+}
+
+/// Returns each module's text with unreachable items removed; modules with nothing left are empty
+pub fn prune(roots: &Usage, modules: &[(String, SourceFile)]) -> Vec<(String, String)> {
+    let mut members: Vec<Vec<Option<SourceFile>>> = Vec::new();
+    let mut units = Vec::new();
+    for (module_index, (_, source)) in modules.iter().enumerate() {
+        let mut module_members = Vec::new();
+        for (item_index, item) in source.items.iter().enumerate() {
+            let body = inherent_impl_body(source, item);
+            if source.is_test_only(item) || item.kind == ItemKind::InnerAttribute {
+                module_members.push(None);
+                continue;
+            }
+            match &body {
+                Some(body) => {
+                    let self_type = impl_types(source, item).0;
+                    for (member_index, member) in body.items.iter().enumerate() {
+                        if body.is_test_only(member) {
+                            continue;
+                        }
+                        units.push(Unit {
+                            module: module_index,
+                            item: item_index,
+                            member: Some(member_index),
+                            name: member.name.clone(),
+                            self_type: self_type.clone(),
+                            trait_name: None,
+                            methods: Vec::new(),
+                            mentions: Vec::new(),
+                            live: false,
+                        });
+                    }
+                }
+                None => {
+                    let (self_type, trait_name) = if item.kind == ItemKind::Impl {
+                        impl_types(source, item)
+                    } else {
+                        (None, None)
+                    };
+                    units.push(Unit {
+                        module: module_index,
+                        item: item_index,
+                        member: None,
+                        name: item_name(source, item),
+                        self_type,
+                        trait_name,
+                        methods: trait_methods(source, item),
+                        mentions: macro_mentions(modules, item),
+                        live: false,
+                    });
+                }
+            }
+            module_members.push(body);
+        }
+        members.push(module_members);
+    }
+
+    let defined: HashSet<String> = units
+        .iter()
+        .filter(|unit| modules[unit.module].1.items[unit.item].kind != ItemKind::Use)
+        .filter_map(|unit| unit.name.clone())
+        .collect();
+    let types: HashSet<String> = modules
+        .iter()
+        .flat_map(|(_, source)| source.items.iter())
+        .filter(|item| {
+            matches!(
+                item.kind,
+                ItemKind::Struct | ItemKind::Enum | ItemKind::Union
+            )
+        })
+        .filter_map(|item| item.name.clone())
+        .collect();
+    let mut usage = Usage {
+        idents: roots.idents.clone(),
+        paths: roots.paths.clone(),
+        operators: roots.operators.clone(),
+    };
+
+    loop {
+        let mut changed = false;
+        for unit in units.iter_mut().filter(|unit| !unit.live) {
+            let source = &modules[unit.module].1;
+            let item = &source.items[unit.item];
+            if !is_reachable(unit, item, &defined, &usage) {
+                continue;
+            }
+            unit.live = true;
+            changed = true;
+            match unit.member {
+                Some(member) => {
+                    let body = members[unit.module][unit.item].as_ref().unwrap();
+                    let member = &body.items[member];
+                    let typing = Typing {
+                        types: &types,
+                        self_is_typed: true,
+                    };
+                    usage.add_tokens(body, member.header..member.tokens.end, Some(&typing));
+                }
+                None => {
+                    let typing = Typing {
+                        types: &types,
+                        self_is_typed: unit.self_type.as_ref().is_some_and(|t| types.contains(t)),
+                    };
+                    usage.add_tokens(source, item.header..item.tokens.end, Some(&typing));
+                    // Macros that generate operator impls call trait methods passed in as
+                    // arguments, e.g. `ops!(Add add AddAssign add_assign)` expanding to
+                    // `AddAssign::add_assign(&mut lhs, &rhs)`, which uses the `&rhs` impls
+                    if item.kind == ItemKind::MacroCall {
+                        let arguments = source.macro_arguments(item);
+                        usage
+                            .paths
+                            .extend(arguments.into_iter().map(str::to_string));
+                    }
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    modules
+        .iter()
+        .enumerate()
+        .map(|(module_index, (name, source))| {
+            let mut text = String::new();
+            for (item_index, item) in source.items.iter().enumerate() {
+                let item_units: Vec<&Unit> = units
+                    .iter()
+                    .filter(|unit| unit.module == module_index && unit.item == item_index)
+                    .collect();
+                if !item_units.iter().any(|unit| unit.live) {
+                    continue;
+                }
+                match &members[module_index][item_index] {
+                    Some(body) => {
+                        let open = impl_open_brace(source, item);
+                        text.push_str(&source.text[item.span.start..source.tokens[open].end]);
+                        for unit in item_units.iter().filter(|unit| unit.live) {
+                            text.push_str(body.item_text(&body.items[unit.member.unwrap()]));
+                        }
+                        text.push_str("\n}");
+                    }
+                    None => text.push_str(source.item_text(item)),
+                }
+            }
+            (name.clone(), text.trim_matches('\n').to_string())
+        })
+        .collect()
+}
+
+fn is_reachable(unit: &Unit, item: &Item, defined: &HashSet<String>, usage: &Usage) -> bool {
+    let named = |name: &Option<String>| name.as_ref().is_some_and(|n| usage.idents.contains(n));
+    let available = |name: &Option<String>| {
+        name.as_ref()
+            .is_none_or(|n| !defined.contains(n) || usage.idents.contains(n))
+    };
+
+    if unit.member.is_some() {
+        return named(&unit.self_type) && named(&unit.name);
+    }
+    match item.kind {
+        ItemKind::Impl => {
+            let operator = OPERATOR_TRAITS
+                .iter()
+                .find(|(name, _, _)| unit.trait_name.as_deref() == Some(name));
+            let operator_used = operator.is_none_or(|(_, operator, method)| {
+                usage.operators.contains(*operator) || usage.paths.contains(*method)
+            });
+            unit.self_type.is_some()
+                && available(&unit.self_type)
+                && available(&unit.trait_name)
+                && operator_used
+        }
+        // `use` items only go when they re-export something that is itself gone
+        ItemKind::Use => available(&unit.name),
+        // What a top-level macro call generates, e.g. trait impls for a list of types, is not
+        // named anywhere, so the call stays when the macro's definition mentions something live
+        ItemKind::MacroCall => unit
+            .mentions
+            .iter()
+            .any(|name| defined.contains(name) && usage.idents.contains(name)),
+        ItemKind::Trait => {
+            named(&unit.name)
+                || unit
+                    .methods
+                    .iter()
+                    .any(|method| usage.paths.contains(method))
+        }
+        _ => named(&unit.name),
+    }
+}
+
+/// The name an item is reached by; for `use` items, the last name they import
+fn item_name(source: &SourceFile, item: &Item) -> Option<String> {
+    match item.kind {
+        ItemKind::Use => source.idents(item).last().map(str::to_string),
+        _ => item.name.clone(),
+    }
+}
+
+/// For a macro call, the types and traits named in the body of the `macro_rules!` it calls.
+/// Functions and variables are left out since their names are too common to mean anything.
+fn macro_mentions(modules: &[(String, SourceFile)], item: &Item) -> Vec<String> {
+    if item.kind != ItemKind::MacroCall {
+        return Vec::new();
+    }
+    let items = || {
+        modules.iter().flat_map(|(_, module)| {
+            module
+                .items
+                .iter()
+                .map(move |definition| (module, definition))
+        })
+    };
+    let types: HashSet<&str> = items()
+        .filter(|(_, definition)| {
+            matches!(
+                definition.kind,
+                ItemKind::Struct
+                    | ItemKind::Enum
+                    | ItemKind::Union
+                    | ItemKind::Trait
+                    | ItemKind::Type
+            )
+        })
+        .filter_map(|(_, definition)| definition.name.as_deref())
+        .collect();
+    items()
+        .filter(|(_, definition)| {
+            definition.kind == ItemKind::MacroRules && definition.name == item.name
+        })
+        .flat_map(|(module, definition)| module.idents(definition))
+        .filter(|name| types.contains(name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The names of the functions a trait declares
+fn trait_methods(source: &SourceFile, item: &Item) -> Vec<String> {
+    if item.kind != ItemKind::Trait {
+        return Vec::new();
+    }
+    (item.header..item.tokens.end.saturating_sub(1))
+        .filter(|&index| source.token_text(index) == "fn")
+        .map(|index| source.token_text(index + 1).to_string())
+        .collect()
+}
+
+/// The type an impl is for and the trait it implements, by their last path segment
+fn impl_types(source: &SourceFile, item: &Item) -> (Option<String>, Option<String>) {
+    let open = impl_open_brace(source, item);
+    let mut index = item.header;
+    while source.token_text(index) != "impl" {
+        index += 1;
+    }
+    index = skip_generics(source, index + 1);
+
+    let mut first_path = None;
+    let mut second_path = None;
+    let mut in_second = false;
+    let mut depth = 0usize;
+    while index < open {
+        let text = source.token_text(index);
+        match text {
+            "<" => depth += 1,
+            ">" => depth = depth.saturating_sub(1),
+            "for" if depth == 0 => in_second = true,
+            "where" if depth == 0 => break,
+            _ if depth == 0 && source.tokens[index].kind == TokenKind::Ident => {
+                if text != "mut" && text != "dyn" {
+                    if in_second {
+                        second_path = Some(text.to_string());
+                    } else {
+                        first_path = Some(text.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    if in_second {
+        (second_path, first_path)
+    } else {
+        (first_path, None)
+    }
+}
+
+fn skip_generics(source: &SourceFile, index: usize) -> usize {
+    if source.token_text(index) != "<" {
+        return index;
+    }
+    let mut depth = 0usize;
+    let mut index = index;
+    loop {
+        match source.token_text(index) {
+            "<" => depth += 1,
+            ">" => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+}
+
+fn impl_open_brace(source: &SourceFile, item: &Item) -> usize {
+    (item.header..item.tokens.end)
+        .find(|&index| source.token_text(index) == "{")
+        .unwrap()
+}
+
+/// The members of an `impl Type { ... }` block, parsed as items of their own
+fn inherent_impl_body(source: &SourceFile, item: &Item) -> Option<SourceFile> {
+    if item.kind != ItemKind::Impl || impl_types(source, item).1.is_some() {
+        return None;
+    }
+    let open = impl_open_brace(source, item);
+    let close = item.tokens.end - 1;
+    let body = &source.text[source.tokens[open].end..source.tokens[close].start];
+    SourceFile::parse(body.to_string()).ok()
+}
+
+#[cfg(test)]
+mod prune_tests {
+    use super::*;
+
+    fn prune_one(solution: &str, module: &str) -> String {
+        let roots = Usage::of_source(&SourceFile::parse(solution.to_string()).unwrap());
+        let modules = vec![(
+            "module".to_string(),
+            SourceFile::parse(module.to_string()).unwrap(),
+        )];
+        prune(&roots, &modules).remove(0).1
+    }
+
+    #[test]
+    fn test_prune_keeps_extension_traits_called_as_methods() {
+        let module = "trait Average: Iterator {\n    fn average(self) -> f32;\n}\n\nimpl<T: Iterator> Average for T {\n    fn average(self) -> f32 { 0.0 }\n}\n\ntrait Unused {\n    fn unused(&self);\n}";
+        assert_eq!(
+            prune_one("fn main() { [1].iter().average(); }", module),
+            "trait Average: Iterator {\n    fn average(self) -> f32;\n}\n\nimpl<T: Iterator> Average for T {\n    fn average(self) -> f32 { 0.0 }\n}"
+        );
+    }
+
+    #[test]
+    fn test_prune_keeps_macro_generated_items() {
+        let module = "pub trait Scan { fn scan() -> Self; }\n\nmacro_rules! scan_int {\n    ($($int:ty),*) => {$(impl Scan for $int { fn scan() -> Self { 0 } })*};\n}\nscan_int!(u8, u64);\n\nmacro_rules! unused { () => {}; }";
+        assert_eq!(
+            prune_one("fn main() { <u64 as Scan>::scan(); }", module),
+            "pub trait Scan { fn scan() -> Self; }\n\nmacro_rules! scan_int {\n    ($($int:ty),*) => {$(impl Scan for $int { fn scan() -> Self { 0 } })*};\n}\nscan_int!(u8, u64);"
+        );
+        assert_eq!(prune_one("fn main() {}", module), "");
+    }
+
+    #[test]
+    fn test_usage_operators() {
+        let usage = Usage::of_source(
+            &SourceFile::parse("fn f() -> u8 { a /= b; c * d; println!(\"\") }".to_string())
+                .unwrap(),
+        );
+        let mut operators: Vec<&str> = usage.operators.iter().map(String::as_str).collect();
+        operators.sort();
+        assert_eq!(operators, vec!["(", ")", "*", "->", "/=", ";", "{", "}"]);
+    }
+
+    #[test]
+    fn test_prune_functions_transitively() {
+        let module =
+            "pub fn read_vec() {}\n\npub fn read_array() { read_vec() }\n\npub fn read_str() {}";
+        assert_eq!(
+            prune_one("fn main() { read_array(); }", module),
+            "pub fn read_vec() {}\n\npub fn read_array() { read_vec() }"
+        );
+        assert_eq!(prune_one("fn main() {}", module), "");
+    }
+
+    #[test]
+    fn test_prune_operator_impls() {
+        let module = "pub struct Big;\nimpl std::ops::Add for Big { type Output = Big; fn add(self, _: Big) -> Big { self } }\nimpl std::ops::Div<&Big> for Big { type Output = Big; fn div(self, _: &Big) -> Big { self } }";
+        let pruned = prune_one("fn main() { let x = Big + Big; }", module);
+        assert!(pruned.contains("Add for Big"));
+        assert!(!pruned.contains("Div<&Big> for Big"));
+    }
+
+    #[test]
+    fn test_prune_keeps_impls_called_by_live_operator_macros() {
+        let module = "pub struct Big;\nimpl std::ops::AddAssign<&Big> for Big { fn add_assign(&mut self, _: &Big) {} }\nimpl std::ops::SubAssign<&Big> for Big { fn sub_assign(&mut self, _: &Big) {} }\nmacro_rules! ops {\n    ($($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {$(\n        impl std::ops::$op for Big { type Output = Big; fn $method(mut self, rhs: Big) -> Big { std::ops::$op_assign::$method_assign(&mut self, &rhs); self } }\n    )*};\n}\nops!(Add add AddAssign add_assign);";
+        let pruned = prune_one("fn main() { let x = Big + Big; }", module);
+        assert!(pruned.contains("AddAssign<&Big> for Big"));
+        assert!(!pruned.contains("SubAssign<&Big> for Big"));
+        assert!(pruned.contains("ops!(Add add AddAssign add_assign);"));
+    }
+
+    #[test]
+    fn test_prune_inherent_methods() {
+        let module = "pub struct Big;\n\nimpl Big {\n    pub fn new() -> Big { Big }\n\n    pub fn unused(&self) {}\n}";
+        assert_eq!(
+            prune_one("fn main() { Big::new(); }", module),
+            "pub struct Big;\n\nimpl Big {\n    pub fn new() -> Big { Big }\n}"
+        );
+    }
+
+    #[test]
+    fn test_prune_reexported_macro() {
+        let module = "#[macro_export]\nmacro_rules! kattis_struct { () => {}; }\npub use kattis_struct;\n\npub fn read_one() {}";
+        assert_eq!(
+            prune_one("fn main() { read_one(); }", module),
+            "pub fn read_one() {}"
+        );
+    }
+}
@@ -0,0 +1,477 @@
+/// Runs a crate's binaries against the Kattis sample files kept next to it.
+///
+/// A crate keeps its samples as `samples/<case>.in` with a matching `samples/<case>.ans`. Crates
+/// with several targets keep them per target instead, in `samples/<target>/`, where `<target>` is
+/// either a `src/bin` name or the Kattis id of a problem registered in a `combined_*` crate.
+/// Answers are compared the way Kattis' default validator does, including its float tolerances.
+use crate::manifest::Manifest;
+use crate::workspace::{self, SolutionTarget};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleCase {
+    pub name: String,
+    pub input: PathBuf,
+    pub answer: PathBuf,
+}
+
+/// The `*.in` files in `dir` that have a matching `*.ans`, sorted by name
+pub fn discover(dir: &Path) -> Vec<SampleCase> {
+    let mut cases: Vec<SampleCase> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+        .filter_map(|input| {
+            let answer = input.with_extension("ans");
+            answer.is_file().then(|| SampleCase {
+                name: input.file_stem().unwrap().to_string_lossy().to_string(),
+                input,
+                answer,
+            })
+        })
+        .collect();
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    cases
+}
+
+/// How output tokens are compared, following Kattis' `validator_flags`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Comparison {
+    pub absolute_tolerance: Option<f64>,
+    pub relative_tolerance: Option<f64>,
+    pub case_sensitive: bool,
+    pub space_change_sensitive: bool,
+}
+
+impl Comparison {
+    /// Parses Kattis validator flags, e.g. `float_tolerance 1e-6 case_sensitive`
+    pub fn from_validator_flags(flags: &str) -> Result<Comparison, String> {
+        let mut comparison = Comparison::default();
+        let mut words = flags.split_whitespace();
+        while let Some(flag) = words.next() {
+            let mut tolerance = || {
+                words
+                    .next()
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .ok_or_else(|| format!("{} needs a number", flag))
+            };
+            match flag {
+                "float_tolerance" => {
+                    let value = tolerance()?;
+                    comparison.absolute_tolerance = Some(value);
+                    comparison.relative_tolerance = Some(value);
+                }
+                "float_absolute_tolerance" => comparison.absolute_tolerance = Some(tolerance()?),
+                "float_relative_tolerance" => comparison.relative_tolerance = Some(tolerance()?),
+                "case_sensitive" => comparison.case_sensitive = true,
+                "space_change_sensitive" => comparison.space_change_sensitive = true,
+                _ => return Err(format!("unknown validator flag {}", flag)),
+            }
+        }
+        Ok(comparison)
+    }
+
+    pub fn has_float_tolerance(&self) -> bool {
+        self.absolute_tolerance.is_some() || self.relative_tolerance.is_some()
+    }
+
+    pub fn accepts(&self, expected: &str, actual: &str) -> bool {
+        if self.space_change_sensitive {
+            let expected_lines: Vec<&str> = expected.trim_end().lines().collect();
+            let actual_lines: Vec<&str> = actual.trim_end().lines().collect();
+            return expected_lines.len() == actual_lines.len()
+                && expected_lines
+                    .iter()
+                    .zip(actual_lines.iter())
+                    .all(|(e, a)| self.tokens_match(e, a));
+        }
+        self.tokens_match(expected, actual)
+    }
+
+    fn tokens_match(&self, expected: &str, actual: &str) -> bool {
+        let mut expected_tokens = expected.split_whitespace();
+        let mut actual_tokens = actual.split_whitespace();
+        loop {
+            match (expected_tokens.next(), actual_tokens.next()) {
+                (None, None) => return true,
+                (Some(e), Some(a)) if self.token_matches(e, a) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    fn token_matches(&self, expected: &str, actual: &str) -> bool {
+        let exact = if self.case_sensitive {
+            expected == actual
+        } else {
+            expected.eq_ignore_ascii_case(actual)
+        };
+        if exact || !self.has_float_tolerance() {
+            return exact;
+        }
+        match (expected.parse::<f64>(), actual.parse::<f64>()) {
+            (Ok(e), Ok(a)) if e.is_finite() && a.is_finite() => {
+                let difference = (e - a).abs();
+                self.absolute_tolerance.is_some_and(|tol| difference <= tol)
+                    || self
+                        .relative_tolerance
+                        .is_some_and(|tol| difference <= tol * e.abs())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A line-by-line view of where the output went wrong, at most `limit` differing lines
+pub fn diff(expected: &str, actual: &str, comparison: &Comparison, limit: usize) -> String {
+    let expected_lines: Vec<&str> = expected.trim_end().lines().collect();
+    let actual_lines: Vec<&str> = actual.trim_end().lines().collect();
+    let mut out = String::new();
+    let mut shown = 0usize;
+    for line in 0..expected_lines.len().max(actual_lines.len()) {
+        let e = expected_lines.get(line);
+        let a = actual_lines.get(line);
+        let same = matches!((e, a), (Some(e), Some(a)) if comparison.tokens_match(e, a));
+        if same {
+            continue;
+        }
+        if shown == limit {
+            out.push_str("  ...\n");
+            break;
+        }
+        shown += 1;
+        out.push_str(&format!("  line {}:\n", line + 1));
+        match e {
+            Some(e) => out.push_str(&format!("  - {}\n", e)),
+            None => out.push_str("  - <missing>\n"),
+        }
+        match a {
+            Some(a) => out.push_str(&format!("  + {}\n", a)),
+            None => out.push_str("  + <missing>\n"),
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer(String),
+    RuntimeError(String),
+    TimeLimitExceeded,
+}
+
+pub struct CaseResult {
+    pub case: SampleCase,
+    pub verdict: Verdict,
+    pub elapsed: Duration,
+}
+
+/// What to run for one set of samples: an executable and the arguments that select the problem
+pub struct SampleRun {
+    pub label: String,
+    pub executable: PathBuf,
+    pub args: Vec<String>,
+    pub cases: Vec<SampleCase>,
+    pub comparison: Comparison,
+}
+
+impl SampleRun {
+    pub fn run(&self, timeout: Duration) -> Result<Vec<CaseResult>, String> {
+        self.cases
+            .iter()
+            .map(|case| {
+                let (verdict, elapsed) = run_case(
+                    &self.executable,
+                    &self.args,
+                    case,
+                    &self.comparison,
+                    timeout,
+                )?;
+                Ok(CaseResult {
+                    case: case.clone(),
+                    verdict,
+                    elapsed,
+                })
+            })
+            .collect()
+    }
+}
+
+fn run_case(
+    executable: &Path,
+    args: &[String],
+    case: &SampleCase,
+    comparison: &Comparison,
+    timeout: Duration,
+) -> Result<(Verdict, Duration), String> {
+    let input = std::fs::File::open(&case.input)
+        .map_err(|error| format!("failed to open {}: {}", case.input.display(), error))?;
+    let started = Instant::now();
+    let mut child = std::process::Command::new(executable)
+        .args(args)
+        .stdin(input)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run {}: {}", executable.display(), error))?;
+
+    // Read the pipes on their own threads so a chatty solution cannot block on a full pipe
+    let mut stdout = child.stdout.take().unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        std::io::Read::read_to_end(&mut stdout, &mut buffer).map(|_| buffer)
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        std::io::Read::read_to_end(&mut stderr, &mut buffer).map(|_| buffer)
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|error| error.to_string())? {
+            break Some(status);
+        }
+        if started.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    let elapsed = started.elapsed();
+    let stdout = stdout_reader.join().unwrap().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap().unwrap_or_default();
+
+    let Some(status) = status else {
+        return Ok((Verdict::TimeLimitExceeded, elapsed));
+    };
+    if !status.success() {
+        let message = String::from_utf8_lossy(&stderr).trim_end().to_string();
+        return Ok((Verdict::RuntimeError(message), elapsed));
+    }
+
+    let expected = std::fs::read_to_string(&case.answer)
+        .map_err(|error| format!("failed to read {}: {}", case.answer.display(), error))?;
+    let actual = String::from_utf8_lossy(&stdout);
+    let verdict = if comparison.accepts(&expected, &actual) {
+        Verdict::Accepted
+    } else {
+        Verdict::WrongAnswer(diff(&expected, &actual, comparison, 10))
+    };
+    Ok((verdict, elapsed))
+}
+
+/// The crate's comparison settings from `[package.metadata.kattis]`, e.g.
+/// `validator_flags = "float_tolerance 1e-6"`
+pub fn manifest_comparison(manifest: &Manifest) -> Result<Comparison, String> {
+    manifest
+        .get("package.metadata.kattis", "validator_flags")
+        .map_or(Ok(Comparison::default()), Comparison::from_validator_flags)
+}
+
+/// Finds every set of samples in a crate and what has to run for each of them
+pub fn plan(crate_dir: &Path, profile: &str) -> Result<Vec<SampleRun>, String> {
+    let root = workspace::find_root(crate_dir)?;
+    let manifest = Manifest::read(&crate_dir.join("Cargo.toml"))?;
+    let package = manifest
+        .package_name()
+        .ok_or_else(|| format!("{} has no package name", crate_dir.display()))?;
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("target"))
+        .join(profile);
+    let comparison = manifest_comparison(&manifest)?;
+    let samples_dir = crate_dir.join("samples");
+    let bins = workspace::bin_targets(crate_dir);
+    let has_main = crate_dir.join("src").join("main.rs").is_file();
+
+    let mut runs = Vec::new();
+    let top_level = discover(&samples_dir);
+    if !top_level.is_empty() {
+        let target = SolutionTarget::resolve(crate_dir, None)?;
+        runs.push(SampleRun {
+            label: package.to_string(),
+            executable: target_dir.join(&target.name),
+            args: Vec::new(),
+            cases: top_level,
+            comparison,
+        });
+    }
+
+    let mut subdirs: Vec<PathBuf> = std::fs::read_dir(&samples_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        let name = subdir.file_name().unwrap().to_string_lossy().to_string();
+        let cases = discover(&subdir);
+        if cases.is_empty() {
+            continue;
+        }
+        let comparison = match std::fs::read_to_string(subdir.join("validator_flags")) {
+            Ok(flags) => Comparison::from_validator_flags(&flags)?,
+            Err(_) => comparison,
+        };
+        // A problem inside a combined crate is selected by passing its problem id to main
+        let (executable, args) = if bins.contains(&name) {
+            (target_dir.join(&name), Vec::new())
+        } else if has_main {
+            (target_dir.join(package), vec![name.clone()])
+        } else {
+            return Err(format!(
+                "{} matches neither a bin target nor a problem in src/main.rs",
+                subdir.display()
+            ));
+        };
+        runs.push(SampleRun {
+            label: format!("{}/{}", package, name),
+            executable,
+            args,
+            cases,
+            comparison,
+        });
+    }
+    Ok(runs)
+}
+
+/// Builds every binary of the crate so the sample runs have something to execute
+pub fn build(crate_dir: &Path, release: bool) -> Result<(), String> {
+    let package = workspace::package_name(crate_dir)?;
+    let mut command = std::process::Command::new(std::env::var("CARGO").unwrap_or("cargo".into()));
+    command.args(["build", "--quiet", "--bins", "--package", &package]);
+    if release {
+        command.arg("--release");
+    }
+    let status = command
+        .current_dir(crate_dir)
+        .status()
+        .map_err(|error| format!("failed to run cargo: {}", error))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("cargo build failed for {}", package))
+    }
+}
+
+/// Builds the crate and runs every sample it has, returning how many passed out of how many ran,
+/// or which ones failed
+pub fn verify(crate_dir: &Path, timeout: Duration) -> Result<(usize, usize), String> {
+    let runs = plan(crate_dir, "debug")?;
+    if runs.is_empty() {
+        return Err(format!("{} has no samples", crate_dir.display()));
+    }
+    build(crate_dir, false)?;
+    let (mut passed, mut total) = (0, 0);
+    let mut failed = Vec::new();
+    for run in runs {
+        for result in run.run(timeout)? {
+            total += 1;
+            let label = format!("{} {}", run.label, result.case.name);
+            match result.verdict {
+                Verdict::Accepted => passed += 1,
+                Verdict::WrongAnswer(diff) => {
+                    failed.push(format!("{}: wrong answer\n{}", label, diff.trim_end()))
+                }
+                Verdict::RuntimeError(stderr) => {
+                    failed.push(format!("{}: run time error\n{}", label, stderr))
+                }
+                Verdict::TimeLimitExceeded => {
+                    failed.push(format!("{}: time limit exceeded", label))
+                }
+            }
+        }
+    }
+    if failed.is_empty() {
+        Ok((passed, total))
+    } else {
+        Err(format!("samples failed:\n{}", failed.join("\n")))
+    }
+}
+
+/// Crates in the workspace member directories that have a `samples` directory
+pub fn crates_with_samples(root: &Path) -> Vec<PathBuf> {
+    let mut crates: Vec<PathBuf> = [
+        "completed/hard",
+        "completed/medium",
+        "completed/trivial",
+        "progress",
+    ]
+    .iter()
+    .flat_map(|group| {
+        std::fs::read_dir(root.join(group))
+            .into_iter()
+            .flatten()
+            .flatten()
+    })
+    .map(|entry| entry.path())
+    .filter(|path| path.join("Cargo.toml").is_file() && path.join("samples").is_dir())
+    .collect();
+    crates.sort();
+    crates
+}
+
+#[cfg(test)]
+mod samples_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_comparison_ignores_whitespace_and_case() {
+        let comparison = Comparison::default();
+        assert!(comparison.accepts("1 2\n3\n", "1  2 3"));
+        assert!(comparison.accepts("Yes\n", "yes\n"));
+        assert!(!comparison.accepts("1.0\n", "1.00\n"));
+        assert!(!comparison.accepts("1 2\n", "1 2 3\n"));
+    }
+
+    #[test]
+    fn test_sensitive_comparison() {
+        let comparison =
+            Comparison::from_validator_flags("case_sensitive space_change_sensitive").unwrap();
+        assert!(!comparison.accepts("Yes\n", "yes\n"));
+        assert!(!comparison.accepts("1 2\n3\n", "1 2 3\n"));
+        assert!(comparison.accepts("1 2\n3\n", "1  2\n3"));
+    }
+
+    #[test]
+    fn test_float_tolerance() {
+        let comparison = Comparison::from_validator_flags("float_tolerance 1e-6").unwrap();
+        assert!(comparison.accepts("2.5 1000000\n", "2.5000001 1000000.5\n"));
+        assert!(!comparison.accepts("2.5\n", "2.501\n"));
+        assert!(!comparison.accepts("nan\n", "2.5\n"));
+
+        let absolute = Comparison::from_validator_flags("float_absolute_tolerance 0.01").unwrap();
+        assert!(absolute.accepts("1000\n", "1000.009\n"));
+        assert!(!absolute.accepts("1000\n", "1000.02\n"));
+
+        assert!(Comparison::from_validator_flags("float_tolerance").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let comparison = Comparison::default();
+        assert_eq!(
+            diff("23\n40\n", "23\n41\n", &comparison, 10),
+            "  line 2:\n  - 40\n  + 41\n"
+        );
+        assert_eq!(
+            diff("1\n", "1\n2\n", &comparison, 10),
+            "  line 2:\n  - <missing>\n  + 2\n"
+        );
+    }
+
+    #[test]
+    fn test_discover_workspace_samples() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let cases = discover(&root.join("completed/medium/fenwick/samples"));
+        let names: Vec<&str> = cases.iter().map(|case| case.name.as_str()).collect();
+        assert_eq!(names, vec!["1", "2"]);
+    }
+}
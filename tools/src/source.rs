@@ -0,0 +1,667 @@
+/// A deliberately small Rust lexer and top-level item splitter.
+/// It only understands as much of the grammar as the tools need: where items start and end,
+/// what they are called, and which attributes sit on them. Everything else is kept verbatim.
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Lifetime,
+    Literal,
+    Punct,
+    DocComment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    InnerAttribute,
+    Fn,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Impl,
+    Const,
+    Static,
+    Type,
+    Mod,
+    Use,
+    ExternCrate,
+    MacroRules,
+    MacroCall,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub name: Option<String>,
+    /// Byte range of the item including the comments and blank lines that precede it
+    pub span: Range<usize>,
+    /// Index range into `SourceFile::tokens`, starting at the first attribute or doc comment
+    pub tokens: Range<usize>,
+    /// Index of the first token after the outer attributes and doc comments
+    pub header: usize,
+}
+
+pub struct SourceFile {
+    pub text: String,
+    pub tokens: Vec<Token>,
+    pub items: Vec<Item>,
+}
+
+impl SourceFile {
+    pub fn parse(text: String) -> Result<SourceFile, String> {
+        let tokens = tokenize(&text)?;
+        let items = split_items(&text, &tokens)?;
+        Ok(SourceFile {
+            text,
+            tokens,
+            items,
+        })
+    }
+
+    pub fn token_text(&self, index: usize) -> &str {
+        let token = self.tokens[index];
+        &self.text[token.start..token.end]
+    }
+
+    /// Text of the item including its leading trivia
+    pub fn item_text(&self, item: &Item) -> &str {
+        &self.text[item.span.clone()]
+    }
+
+    /// Text of the item from its first attribute to its final token
+    pub fn item_code(&self, item: &Item) -> &str {
+        let start = self.tokens[item.tokens.start].start;
+        let end = self.tokens[item.tokens.end - 1].end;
+        &self.text[start..end]
+    }
+
+    /// Whether the item only exists under `cfg(test)`, i.e. `#[test]`, `#[bench]` or `#[cfg(test)]`
+    pub fn is_test_only(&self, item: &Item) -> bool {
+        self.attributes(item).iter().any(|attribute| {
+            let words: Vec<&str> = attribute
+                .clone()
+                .filter(|&index| self.tokens[index].kind == TokenKind::Ident)
+                .map(|index| self.token_text(index))
+                .collect();
+            match words.first() {
+                Some(&"test") | Some(&"bench") => words.len() == 1,
+                Some(&"cfg") => words.contains(&"test") && !words.contains(&"not"),
+                _ => false,
+            }
+        })
+    }
+
+    /// Token ranges of the outer attributes of an item, excluding the `#[` and `]`
+    pub fn attributes(&self, item: &Item) -> Vec<Range<usize>> {
+        let mut attributes = Vec::new();
+        let mut index = item.tokens.start;
+        while index < item.header {
+            if self.token_text(index) == "#" {
+                let close = matching_close(self, index + 1);
+                attributes.push(index + 2..close);
+                index = close + 1;
+            } else {
+                index += 1;
+            }
+        }
+        attributes
+    }
+
+    /// Identifier tokens of an item, in order, excluding attributes and doc comments
+    pub fn idents<'a>(&'a self, item: &Item) -> impl Iterator<Item = &'a str> + 'a {
+        (item.header..item.tokens.end)
+            .filter(|&index| self.tokens[index].kind == TokenKind::Ident)
+            .map(|index| self.token_text(index))
+    }
+
+    /// For `name!(...)` and `name! { ... }` calls, the identifier tokens inside the delimiters
+    pub fn macro_arguments(&self, item: &Item) -> Vec<&str> {
+        if item.kind != ItemKind::MacroCall {
+            return Vec::new();
+        }
+        (item.header + 2..item.tokens.end)
+            .filter(|&index| self.tokens[index].kind == TokenKind::Ident)
+            .map(|index| self.token_text(index))
+            .collect()
+    }
+}
+
+fn matching_close(source: &SourceFile, open: usize) -> usize {
+    let mut depth = 0usize;
+    for index in open..source.tokens.len() {
+        match source.token_text(index) {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+    source.tokens.len() - 1
+}
+
+pub fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut index = 0usize;
+
+    while index < bytes.len() {
+        let start = index;
+        let b = bytes[index];
+
+        if b.is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if bytes[index..].starts_with(b"//") {
+            let end = text[index..]
+                .find('\n')
+                .map_or(bytes.len(), |offset| index + offset);
+            let comment = &text[index..end];
+            if (comment.starts_with("///") && !comment.starts_with("////"))
+                || comment.starts_with("//!")
+            {
+                tokens.push(Token {
+                    kind: TokenKind::DocComment,
+                    start,
+                    end,
+                });
+            }
+            index = end;
+            continue;
+        }
+
+        if bytes[index..].starts_with(b"/*") {
+            let mut depth = 0usize;
+            while index < bytes.len() {
+                if bytes[index..].starts_with(b"/*") {
+                    depth += 1;
+                    index += 2;
+                } else if bytes[index..].starts_with(b"*/") {
+                    depth -= 1;
+                    index += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    index += 1;
+                }
+            }
+            if depth != 0 {
+                return Err(format!("unterminated block comment at byte {}", start));
+            }
+            let comment = &text[start..index];
+            if (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+                || comment.starts_with("/*!")
+            {
+                tokens.push(Token {
+                    kind: TokenKind::DocComment,
+                    start,
+                    end: index,
+                });
+            }
+            continue;
+        }
+
+        // Raw strings and prefixed strings have to be checked before identifiers
+        if let Some(end) = lex_prefixed_string(text, index)? {
+            tokens.push(Token {
+                kind: TokenKind::Literal,
+                start,
+                end,
+            });
+            index = end;
+            continue;
+        }
+
+        if b == b'_' || b.is_ascii_alphabetic() || b >= 0x80 {
+            if bytes[index..].starts_with(b"r#") {
+                index += 2;
+            }
+            while index < bytes.len() {
+                let c = bytes[index];
+                if c == b'_' || c.is_ascii_alphanumeric() || c >= 0x80 {
+                    index += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident,
+                start,
+                end: index,
+            });
+            continue;
+        }
+
+        if b.is_ascii_digit() {
+            index += 1;
+            while index < bytes.len() {
+                let c = bytes[index];
+                let next_is_digit = bytes.get(index + 1).is_some_and(|d| d.is_ascii_digit());
+                let is_fraction = c == b'.' && next_is_digit;
+                let is_exponent_sign = (c == b'+' || c == b'-')
+                    && matches!(bytes[index - 1], b'e' | b'E')
+                    && !text[start..].starts_with("0x")
+                    && next_is_digit;
+                if c == b'_' || c.is_ascii_alphanumeric() || is_fraction || is_exponent_sign {
+                    index += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Literal,
+                start,
+                end: index,
+            });
+            continue;
+        }
+
+        if b == b'"' {
+            index = lex_quoted(text, index, b'"')?;
+            tokens.push(Token {
+                kind: TokenKind::Literal,
+                start,
+                end: index,
+            });
+            continue;
+        }
+
+        if b == b'\'' {
+            let rest = &text[index + 1..];
+            let mut chars = rest.char_indices();
+            let is_char_literal = match chars.next() {
+                Some((_, '\\')) => true,
+                Some((_, _)) => chars.next().is_some_and(|(_, c)| c == '\''),
+                None => false,
+            };
+            if is_char_literal {
+                index = lex_quoted(text, index, b'\'')?;
+                tokens.push(Token {
+                    kind: TokenKind::Literal,
+                    start,
+                    end: index,
+                });
+            } else {
+                index += 1;
+                while index < bytes.len()
+                    && (bytes[index] == b'_' || bytes[index].is_ascii_alphanumeric())
+                {
+                    index += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Lifetime,
+                    start,
+                    end: index,
+                });
+            }
+            continue;
+        }
+
+        let width = text[index..].chars().next().map_or(1, char::len_utf8);
+        index += width;
+        tokens.push(Token {
+            kind: TokenKind::Punct,
+            start,
+            end: index,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Lexes `"..."` or `'...'` starting at the opening quote, returning the index after the closing quote
+fn lex_quoted(text: &str, open: usize, quote: u8) -> Result<usize, String> {
+    let bytes = text.as_bytes();
+    let mut index = open + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            c if c == quote => return Ok(index + 1),
+            _ => index += 1,
+        }
+    }
+    Err(format!("unterminated literal at byte {}", open))
+}
+
+/// Lexes `r"..."`, `r#"..."#`, `b"..."`, `br"..."`, `b'.'`, `c"..."` and `cr"..."` literals
+fn lex_prefixed_string(text: &str, start: usize) -> Result<Option<usize>, String> {
+    let rest = &text[start..];
+    let prefix_len = ["br", "cr", "b", "c", "r"]
+        .iter()
+        .find(|prefix| rest.starts_with(*prefix))
+        .map_or(0, |prefix| prefix.len());
+    if prefix_len == 0 {
+        return Ok(None);
+    }
+
+    // Identifiers such as `bar` or `count` also start with these letters
+    if start > 0 {
+        let before = text.as_bytes()[start - 1];
+        if before == b'_' || before.is_ascii_alphanumeric() {
+            return Ok(None);
+        }
+    }
+
+    let prefix = &rest[..prefix_len];
+    let after = &rest[prefix_len..];
+    if prefix.ends_with('r') {
+        let hashes = after.bytes().take_while(|&b| b == b'#').count();
+        if after.as_bytes().get(hashes) != Some(&b'"') {
+            return Ok(None);
+        }
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let body_start = start + prefix_len + hashes + 1;
+        return match text[body_start..].find(&terminator) {
+            Some(offset) => Ok(Some(body_start + offset + terminator.len())),
+            None => Err(format!("unterminated raw string at byte {}", start)),
+        };
+    }
+
+    match after.as_bytes().first() {
+        Some(b'"') => lex_quoted(text, start + prefix_len, b'"').map(Some),
+        Some(b'\'') if prefix == "b" => lex_quoted(text, start + prefix_len, b'\'').map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn split_items(text: &str, tokens: &[Token]) -> Result<Vec<Item>, String> {
+    let token_text = |index: usize| &text[tokens[index].start..tokens[index].end];
+    let mut items = Vec::new();
+    let mut trivia_start = 0usize;
+    let mut index = 0usize;
+
+    while index < tokens.len() {
+        let first = index;
+
+        // Inner attributes, `#![...]`, are items of their own
+        if token_text(index) == "#" && index + 1 < tokens.len() && token_text(index + 1) == "!" {
+            let close = skip_group(tokens, text, index + 2)?;
+            items.push(Item {
+                kind: ItemKind::InnerAttribute,
+                name: None,
+                span: trivia_start..tokens[close].end,
+                tokens: first..close + 1,
+                header: first,
+            });
+            trivia_start = tokens[close].end;
+            index = close + 1;
+            continue;
+        }
+
+        // Outer attributes and doc comments belong to the item that follows them
+        while index < tokens.len() {
+            if tokens[index].kind == TokenKind::DocComment {
+                index += 1;
+            } else if token_text(index) == "#" {
+                index = skip_group(tokens, text, index + 1)? + 1;
+            } else {
+                break;
+            }
+        }
+        let header = index;
+        if header >= tokens.len() {
+            return Err("attribute or doc comment is not followed by an item".to_string());
+        }
+
+        let (kind, name) = classify(text, tokens, header);
+        // `use a::{b, c};` has a brace group before its semicolon, like a const's initializer
+        let ends_with_semicolon =
+            matches!(kind, ItemKind::Const | ItemKind::Static | ItemKind::Use);
+
+        let mut depth = 0usize;
+        let mut end = None;
+        while index < tokens.len() {
+            match token_text(index) {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" => depth = depth.saturating_sub(1),
+                "}" => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0
+                        && !ends_with_semicolon
+                        && !is_parenthesized_call(text, tokens, header)
+                    {
+                        end = Some(index);
+                        break;
+                    }
+                }
+                ";" if depth == 0 => {
+                    end = Some(index);
+                    break;
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+        let end = end.ok_or_else(|| format!("item at byte {} never ends", tokens[first].start))?;
+
+        items.push(Item {
+            kind,
+            name,
+            span: trivia_start..tokens[end].end,
+            tokens: first..end + 1,
+            header,
+        });
+        trivia_start = tokens[end].end;
+        index = end + 1;
+    }
+
+    Ok(items)
+}
+
+/// `name!(...);` and `name![...];` end at the semicolon even when they contain braces
+fn is_parenthesized_call(text: &str, tokens: &[Token], header: usize) -> bool {
+    let token_text = |index: usize| &text[tokens[index].start..tokens[index].end];
+    header + 2 < tokens.len()
+        && tokens[header].kind == TokenKind::Ident
+        && token_text(header + 1) == "!"
+        && matches!(token_text(header + 2), "(" | "[")
+        && token_text(header) != "macro_rules"
+}
+
+fn skip_group(tokens: &[Token], text: &str, open: usize) -> Result<usize, String> {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        match &text[token.start..token.end] {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(format!(
+        "unbalanced delimiters at byte {}",
+        tokens[open].start
+    ))
+}
+
+fn classify(text: &str, tokens: &[Token], header: usize) -> (ItemKind, Option<String>) {
+    let token_text = |index: usize| tokens.get(index).map_or("", |t| &text[t.start..t.end]);
+    let mut index = header;
+
+    // Visibility and qualifiers do not change what the item is
+    loop {
+        match token_text(index) {
+            "pub" => {
+                index += 1;
+                if token_text(index) == "(" {
+                    while token_text(index) != ")" {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            "unsafe" | "async" | "default" => index += 1,
+            "const" if matches!(token_text(index + 1), "fn" | "unsafe" | "async" | "extern") => {
+                index += 1
+            }
+            "extern" if token_text(index + 1) != "crate" => {
+                index += 1;
+                if tokens
+                    .get(index)
+                    .is_some_and(|t| t.kind == TokenKind::Literal)
+                {
+                    index += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let name_after = |offset: usize| {
+        tokens
+            .get(index + offset)
+            .filter(|t| t.kind == TokenKind::Ident)
+            .map(|t| text[t.start..t.end].to_string())
+    };
+
+    match token_text(index) {
+        "fn" => (ItemKind::Fn, name_after(1)),
+        "struct" => (ItemKind::Struct, name_after(1)),
+        "enum" => (ItemKind::Enum, name_after(1)),
+        "union" => (ItemKind::Union, name_after(1)),
+        "trait" => (ItemKind::Trait, name_after(1)),
+        "impl" => (ItemKind::Impl, None),
+        "const" => (ItemKind::Const, name_after(1)),
+        "static" => {
+            let offset = if token_text(index + 1) == "mut" { 2 } else { 1 };
+            (ItemKind::Static, name_after(offset))
+        }
+        "type" => (ItemKind::Type, name_after(1)),
+        "mod" => (ItemKind::Mod, name_after(1)),
+        "use" => (ItemKind::Use, None),
+        "extern" => (ItemKind::ExternCrate, name_after(2)),
+        "macro_rules" => (ItemKind::MacroRules, name_after(2)),
+        _ if token_text(index + 1) == "!" => (ItemKind::MacroCall, name_after(0)),
+        _ => (ItemKind::Other, None),
+    }
+}
+
+#[cfg(test)]
+mod source_tests {
+    use super::*;
+
+    fn kinds_and_names(text: &str) -> Vec<(ItemKind, Option<String>)> {
+        SourceFile::parse(text.to_string())
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| (item.kind, item.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_skips_comments_and_strings() {
+        let text = "let a = \"}\"; // }\n/* { /* nested */ } */ 'x' 'a r#\"{\"# b'}'";
+        let tokens = tokenize(text).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| &text[t.start..t.end]).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "let", "a", "=", "\"}\"", ";", "'x'", "'a", "r#\"{\"#", "b'}'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_non_ascii_comments() {
+        let text = "/* “quoted” */ fn a() {} // ✓";
+        let tokens = tokenize(text).unwrap();
+        assert_eq!(tokens.len(), 6);
+    }
+
+    #[test]
+    fn test_tokenize_numbers() {
+        let text = "0..10 1.5e-3 0xff_u8 x.0";
+        let tokens = tokenize(text).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| &text[t.start..t.end]).collect();
+        assert_eq!(
+            texts,
+            vec!["0", ".", ".", "10", "1.5e-3", "0xff_u8", "x", ".", "0"]
+        );
+    }
+
+    #[test]
+    fn test_split_items() {
+        let text = r#"#![allow(dead_code)]
+// noinspection
+#[allow(unused)]
+macro_rules! import {
+    ($name:ident) => { mod $name; };
+}
+
+import!(input);
+
+const LIMIT: [u64; 2] = { [1, 2] };
+
+use crate::numbers::{Small, Big};
+
+/// Docs
+pub(crate) fn solve<T>() -> T where T: TryFrom<Vec<u8>, Error = ()> { todo!() }
+
+impl std::fmt::Display for Thing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests;
+"#;
+        assert_eq!(
+            kinds_and_names(text),
+            vec![
+                (ItemKind::InnerAttribute, None),
+                (ItemKind::MacroRules, Some("import".to_string())),
+                (ItemKind::MacroCall, Some("import".to_string())),
+                (ItemKind::Const, Some("LIMIT".to_string())),
+                (ItemKind::Use, None),
+                (ItemKind::Fn, Some("solve".to_string())),
+                (ItemKind::Impl, None),
+                (ItemKind::Mod, Some("tests".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_item_text_keeps_leading_trivia() {
+        let source = SourceFile::parse("fn a() {}\n\n// about b\nfn b() {}\n".to_string()).unwrap();
+        assert_eq!(
+            source.item_text(&source.items[1]),
+            "\n\n// about b\nfn b() {}"
+        );
+        assert_eq!(source.item_code(&source.items[1]), "fn b() {}");
+    }
+
+    #[test]
+    fn test_is_test_only() {
+        let source = SourceFile::parse(
+            "#[test]\nfn a() {}\n#[cfg(all(feature = \"unstable\", test))]\nmod b {}\n#[cfg(not(test))]\nfn c() {}\n#[inline]\nfn d() {}"
+                .to_string(),
+        )
+        .unwrap();
+        let flags: Vec<bool> = source
+            .items
+            .iter()
+            .map(|item| source.is_test_only(item))
+            .collect();
+        assert_eq!(flags, vec![true, true, false, false]);
+    }
+}
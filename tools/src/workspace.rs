@@ -0,0 +1,130 @@
+use crate::manifest::Manifest;
+use std::path::{Path, PathBuf};
+
+/// Walks up from `start` to the Cargo.toml that declares `[workspace]`
+pub fn find_root(start: &Path) -> Result<PathBuf, String> {
+    let start = start
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve {}: {}", start.display(), error))?;
+    start
+        .ancestors()
+        .find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .is_ok_and(|text| text.lines().any(|line| line.trim() == "[workspace]"))
+        })
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("{} is not inside the workspace", start.display()))
+}
+
+pub fn current_root() -> Result<PathBuf, String> {
+    let cwd = std::env::current_dir().map_err(|error| error.to_string())?;
+    find_root(&cwd)
+}
+
+pub fn libfreuden_src(root: &Path) -> PathBuf {
+    root.join("libfreuden").join("src")
+}
+
+/// One compilable solution: a crate's `src/main.rs` or one of its `src/bin/*.rs` targets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionTarget {
+    pub crate_dir: PathBuf,
+    pub source: PathBuf,
+    /// The binary name cargo builds for this target
+    pub name: String,
+    pub is_bin_target: bool,
+}
+
+impl SolutionTarget {
+    /// Resolves a crate directory (optionally with a `src/bin` name) or a path to a `.rs` file
+    pub fn resolve(path: &Path, bin: Option<&str>) -> Result<SolutionTarget, String> {
+        if path.is_file() {
+            let crate_dir = path
+                .ancestors()
+                .skip(1)
+                .find(|dir| dir.join("Cargo.toml").is_file())
+                .ok_or_else(|| format!("{} is not inside a crate", path.display()))?
+                .to_path_buf();
+            let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+            let is_bin_target = path.parent().is_some_and(|dir| dir.ends_with("src/bin"));
+            let name = if is_bin_target {
+                stem
+            } else {
+                package_name(&crate_dir)?
+            };
+            return Ok(SolutionTarget {
+                crate_dir,
+                source: path.to_path_buf(),
+                name,
+                is_bin_target,
+            });
+        }
+
+        if !path.join("Cargo.toml").is_file() {
+            return Err(format!("{} is not a crate directory", path.display()));
+        }
+
+        if let Some(bin) = bin {
+            let source = path.join("src").join("bin").join(format!("{}.rs", bin));
+            if !source.is_file() {
+                return Err(format!(
+                    "{} has no bin target named {}",
+                    path.display(),
+                    bin
+                ));
+            }
+            return Ok(SolutionTarget {
+                crate_dir: path.to_path_buf(),
+                source,
+                name: bin.to_string(),
+                is_bin_target: true,
+            });
+        }
+
+        let main = path.join("src").join("main.rs");
+        if main.is_file() {
+            return Ok(SolutionTarget {
+                crate_dir: path.to_path_buf(),
+                source: main,
+                name: package_name(path)?,
+                is_bin_target: false,
+            });
+        }
+
+        let bins = bin_targets(path);
+        match bins.as_slice() {
+            [only] => SolutionTarget::resolve(path, Some(only)),
+            [] => Err(format!("{} has no binary targets", path.display())),
+            _ => Err(format!(
+                "{} has several bin targets, pick one with --bin: {}",
+                path.display(),
+                bins.join(", ")
+            )),
+        }
+    }
+
+    pub fn manifest(&self) -> Result<Manifest, String> {
+        Manifest::read(&self.crate_dir.join("Cargo.toml"))
+    }
+}
+
+pub fn package_name(crate_dir: &Path) -> Result<String, String> {
+    Manifest::read(&crate_dir.join("Cargo.toml"))?
+        .package_name()
+        .map(str::to_string)
+        .ok_or_else(|| format!("{} has no package name", crate_dir.display()))
+}
+
+/// Names of the `src/bin/*.rs` targets of a crate, sorted
+pub fn bin_targets(crate_dir: &Path) -> Vec<String> {
+    let mut bins: Vec<String> = std::fs::read_dir(crate_dir.join("src").join("bin"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().to_string())
+        .collect();
+    bins.sort();
+    bins
+}